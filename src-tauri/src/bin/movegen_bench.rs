@@ -0,0 +1,129 @@
+//Compares the perft speed of the pin and check based legal move filter against the
+//previous approach of playing every candidate move on a clone of the board.
+//Usage: movegen_bench [depth]
+use std::{
+    env, process,
+    time::{Duration, Instant},
+};
+
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    moves::Move,
+    piece::PieceType,
+};
+
+const POSITIONS: [(&str, &str); 5] = [
+    ("Starting position", STARTING_FEN),
+    (
+        "Kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    ),
+    ("Position 3", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"),
+    (
+        "Position 4",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ),
+    (
+        "Position 5",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ),
+];
+
+fn main() {
+    let depth: u8 = match env::args().nth(1).map(|depth| depth.parse()) {
+        Some(Ok(depth)) if depth > 0 => depth,
+        None => 3,
+        _ => {
+            eprintln!("Usage: movegen_bench [depth]");
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "{:<18} {:>10} {:>12} {:>12} {:>8}",
+        "Position", "Nodes", "Clone", "Pins", "Speedup"
+    );
+
+    let mut clone_total = Duration::ZERO;
+    let mut pins_total = Duration::ZERO;
+
+    for (name, fen) in POSITIONS {
+        let board = Board::from_fen(fen);
+
+        let start = Instant::now();
+        let clone_nodes = clone_perft(&board, depth);
+        let clone_time = start.elapsed();
+
+        let start = Instant::now();
+        let pins_nodes = board.perft(depth);
+        let pins_time = start.elapsed();
+
+        if clone_nodes != pins_nodes {
+            eprintln!(
+                "{}: node counts differ, {} with clones and {} with pins",
+                name, clone_nodes, pins_nodes
+            );
+            process::exit(1);
+        }
+
+        println!(
+            "{:<18} {:>10} {:>12.2?} {:>12.2?} {:>7.1}x",
+            name,
+            pins_nodes,
+            clone_time,
+            pins_time,
+            clone_time.as_secs_f64() / pins_time.as_secs_f64()
+        );
+
+        clone_total += clone_time;
+        pins_total += pins_time;
+    }
+
+    println!(
+        "{:<18} {:>10} {:>12.2?} {:>12.2?} {:>7.1}x",
+        "Total",
+        "",
+        clone_total,
+        pins_total,
+        clone_total.as_secs_f64() / pins_total.as_secs_f64()
+    );
+}
+
+//Perft with the legality check generate_moves used to do: every pseudo-legal move
+//is played on a clone and dropped if one of the replies captures the king
+fn clone_perft(board: &Board, depth: u8) -> u64 {
+    let legal_moves: Vec<Move> = board
+        .get_possible_moves()
+        .iter()
+        .filter(|mv| !leaves_king_in_check(board, **mv))
+        .copied()
+        .collect();
+
+    if depth <= 1 {
+        return legal_moves.len() as u64;
+    }
+
+    legal_moves
+        .iter()
+        .map(|mv| {
+            let mut cloned_board = board.clone();
+            cloned_board
+                .make_move(*mv, true)
+                .expect("Only moves from the move list are played");
+            clone_perft(&cloned_board, depth - 1)
+        })
+        .sum()
+}
+
+fn leaves_king_in_check(board: &Board, mv: Move) -> bool {
+    let mut cloned_board = board.clone();
+
+    if cloned_board.make_move(mv, true).is_err() {
+        return true;
+    }
+
+    cloned_board
+        .get_possible_moves()
+        .iter()
+        .any(|reply| cloned_board.get_piece_type(reply.to) == PieceType::King)
+}
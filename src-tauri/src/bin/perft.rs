@@ -0,0 +1,49 @@
+//Prints the perft node count below each legal move in the same format as
+//Stockfish's "go perft", so the output can be diffed against reference engines.
+//Usage: perft <depth> [fen]
+use std::{env, process};
+
+use rust_chess::game::board::{Board, STARTING_FEN};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let depth: u8 = match args.first().map(|depth| depth.parse()) {
+        Some(Ok(depth)) => depth,
+        _ => {
+            eprintln!("Usage: perft <depth> [fen]");
+            process::exit(1);
+        }
+    };
+
+    let fen = if args.len() > 1 {
+        args[1..].join(" ")
+    } else {
+        String::from(STARTING_FEN)
+    };
+
+    let board = match Board::try_from_fen(&fen) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut divide: Vec<(String, u64)> = board
+        .divide(depth)
+        .into_iter()
+        .map(|(mv, nodes)| (mv.to_uci(false), nodes))
+        .collect();
+    divide.sort();
+
+    for (mv, nodes) in &divide {
+        println!("{}: {}", mv, nodes);
+    }
+
+    println!();
+    println!(
+        "Nodes searched: {}",
+        divide.iter().map(|(_, nodes)| nodes).sum::<u64>()
+    );
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use super::{
+    bitboard::{squares, BitBoard},
+    board::Board,
+    piece::Team,
+    square::Square,
+};
+
+//How many pieces of one team attack each square of the board
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttackMap {
+    team: Team,
+    //Attacker counts by square index
+    counts: Vec<u8>,
+}
+
+impl AttackMap {
+    pub fn get_team(&self) -> Team {
+        self.team
+    }
+
+    pub fn get_count(&self, square: Square) -> u8 {
+        self.counts[square.index()]
+    }
+
+    pub fn is_attacked(&self, square: Square) -> bool {
+        self.get_count(square) > 0
+    }
+}
+
+impl Board {
+    //Whether a piece of by_team could capture on the square. Squares holding
+    //by_team's own pieces count too, as they are defended
+    pub fn is_attacked(&self, square: Square, by_team: Team) -> bool {
+        BitBoard::from_board(self).is_square_attacked(square, by_team)
+    }
+
+    //The squares of the pieces of team attacking the square, in board order from a8
+    pub fn attackers_of(&self, square: Square, team: Team) -> Vec<Square> {
+        let mut attackers: Vec<Square> =
+            squares(BitBoard::from_board(self).attackers_of(square, team)).collect();
+
+        attackers.sort_by_key(|attacker| attacker.index());
+        attackers
+    }
+
+    pub fn get_attack_map(&self, team: Team) -> AttackMap {
+        AttackMap {
+            team,
+            counts: BitBoard::from_board(self).attack_counts(team).to_vec(),
+        }
+    }
+}
@@ -0,0 +1,718 @@
+use std::sync::OnceLock;
+
+use super::{
+    board::{Board, CASTLING_RIGHTS},
+    moves::Move,
+    piece::{PieceType, Team},
+    square::Square,
+};
+
+//Bitboards follow the usual engine layout where bit 0 is a1 and bit 63 is h8, unlike
+//Square indices which start at a8. bit and squares convert between the two
+
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+const PROMOTION_PIECES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+const KING_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+//Random number seeds for each rank that make the magic search finish quickly, from Stockfish
+const MAGIC_SEEDS: [u64; 8] = [728, 10316, 55013, 32803, 12281, 15100, 16645, 255];
+
+pub fn bit(square: Square) -> u64 {
+    1 << (square.index() ^ 56)
+}
+
+pub fn squares(mut bits: u64) -> impl Iterator<Item = Square> {
+    std::iter::from_fn(move || {
+        if bits == 0 {
+            return None;
+        }
+
+        let index = bits.trailing_zeros() as usize;
+        bits &= bits - 1;
+        Square::new(index ^ 56)
+    })
+}
+
+pub fn knight_attacks(square: Square) -> u64 {
+    tables().knight[square.index()]
+}
+
+pub fn king_attacks(square: Square) -> u64 {
+    tables().king[square.index()]
+}
+
+//The squares a pawn of the team standing on the square can capture on
+pub fn pawn_attacks(team: Team, square: Square) -> u64 {
+    tables().pawn[team_index(team)][square.index()]
+}
+
+pub fn rook_attacks(square: Square, occupied: u64) -> u64 {
+    let tables = tables();
+    tables.rook[square.index()].lookup(&tables.sliding, occupied)
+}
+
+pub fn bishop_attacks(square: Square, occupied: u64) -> u64 {
+    let tables = tables();
+    tables.bishop[square.index()].lookup(&tables.sliding, occupied)
+}
+
+pub fn queen_attacks(square: Square, occupied: u64) -> u64 {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+pub fn piece_attacks(piece_type: PieceType, team: Team, square: Square, occupied: u64) -> u64 {
+    match piece_type {
+        PieceType::Pawn => pawn_attacks(team, square),
+        PieceType::Knight => knight_attacks(square),
+        PieceType::Bishop => bishop_attacks(square, occupied),
+        PieceType::Rook => rook_attacks(square, occupied),
+        PieceType::Queen => queen_attacks(square, occupied),
+        PieceType::King => king_attacks(square),
+        PieceType::Empty => 0,
+    }
+}
+
+fn team_index(team: Team) -> usize {
+    match team {
+        Team::White => 0,
+        Team::Black => 1,
+    }
+}
+
+fn piece_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+        PieceType::Empty => panic!("Empty squares have no bitboard"),
+    }
+}
+
+struct Magic {
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    fn lookup(&self, table: &[u64], occupied: u64) -> u64 {
+        let index = (occupied & self.mask).wrapping_mul(self.magic) >> self.shift;
+        table[self.offset + index as usize]
+    }
+}
+
+struct AttackTables {
+    knight: [u64; 64],
+    king: [u64; 64],
+    pawn: [[u64; 64]; 2],
+    rook: Vec<Magic>,
+    bishop: Vec<Magic>,
+    //Sliding attacks for every relevant occupancy of every square, indexed through the magics
+    sliding: Vec<u64>,
+}
+
+fn tables() -> &'static AttackTables {
+    static TABLES: OnceLock<AttackTables> = OnceLock::new();
+    TABLES.get_or_init(AttackTables::new)
+}
+
+impl AttackTables {
+    fn new() -> Self {
+        let mut knight = [0; 64];
+        let mut king = [0; 64];
+        let mut pawn = [[0; 64]; 2];
+
+        for square in Square::all() {
+            knight[square.index()] = offset_attacks(square, &KNIGHT_OFFSETS);
+            king[square.index()] = offset_attacks(square, &KING_OFFSETS);
+            pawn[0][square.index()] = offset_attacks(square, &[(-1, -1), (-1, 1)]);
+            pawn[1][square.index()] = offset_attacks(square, &[(1, -1), (1, 1)]);
+        }
+
+        let mut sliding = Vec::new();
+
+        let rook = Square::all()
+            .map(|square| find_magic(square, &ROOK_DIRECTIONS, &mut sliding))
+            .collect();
+        let bishop = Square::all()
+            .map(|square| find_magic(square, &BISHOP_DIRECTIONS, &mut sliding))
+            .collect();
+
+        AttackTables {
+            knight,
+            king,
+            pawn,
+            rook,
+            bishop,
+            sliding,
+        }
+    }
+}
+
+fn offset_attacks(square: Square, offsets: &[(i32, i32)]) -> u64 {
+    offsets
+        .iter()
+        .filter_map(|(row, col)| square.offset(*row, *col))
+        .fold(0, |attacks, target| attacks | bit(target))
+}
+
+//Walks each direction until it leaves the board or hits an occupied square
+fn slow_sliding_attacks(square: Square, directions: &[(i32, i32)], occupied: u64) -> u64 {
+    let mut attacks = 0;
+
+    for (row, col) in directions {
+        let mut current = square;
+
+        while let Some(target) = current.offset(*row, *col) {
+            attacks |= bit(target);
+            if occupied & bit(target) != 0 {
+                break;
+            }
+            current = target;
+        }
+    }
+
+    attacks
+}
+
+//The squares whose occupancy changes the attacks, leaving out the last square of each
+//ray since a blocker there doesn't hide anything
+fn relevant_occupancy(square: Square, directions: &[(i32, i32)]) -> u64 {
+    let mut mask = 0;
+
+    for (row, col) in directions {
+        let mut current = square;
+
+        while let Some(target) = current.offset(*row, *col) {
+            if target.offset(*row, *col).is_none() {
+                break;
+            }
+            mask |= bit(target);
+            current = target;
+        }
+    }
+
+    mask
+}
+
+//Searches random sparse numbers until one maps every occupancy of the mask to an index
+//without mixing up occupancies that have different attacks
+fn find_magic(square: Square, directions: &[(i32, i32)], table: &mut Vec<u64>) -> Magic {
+    let mut rng = XorShift(MAGIC_SEEDS[square.rank() as usize]);
+    let mask = relevant_occupancy(square, directions);
+    let shift = 64 - mask.count_ones();
+
+    let mut occupancies = Vec::new();
+    let mut subset: u64 = 0;
+    loop {
+        occupancies.push((subset, slow_sliding_attacks(square, directions, subset)));
+        subset = subset.wrapping_sub(mask) & mask;
+        if subset == 0 {
+            break;
+        }
+    }
+
+    let mut attacks = vec![0; 1 << mask.count_ones()];
+    let mut attempts = vec![0; attacks.len()];
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        let magic = rng.next() & rng.next() & rng.next();
+
+        if (mask.wrapping_mul(magic) >> 56).count_ones() < 6 {
+            continue;
+        }
+
+        let fits = occupancies.iter().all(|(occupied, occupied_attacks)| {
+            let index = (occupied.wrapping_mul(magic) >> shift) as usize;
+
+            if attempts[index] != attempt {
+                attempts[index] = attempt;
+                attacks[index] = *occupied_attacks;
+                true
+            } else {
+                attacks[index] == *occupied_attacks
+            }
+        });
+
+        if fits {
+            let offset = table.len();
+            table.extend_from_slice(&attacks);
+
+            return Magic {
+                mask,
+                magic,
+                shift,
+                offset,
+            };
+        }
+    }
+}
+
+//xorshift64* random number generator
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+//A position stored as one set of squares per piece type and per team, generating the
+//same legal moves as Board with table lookups instead of walking the squares
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitBoard {
+    pieces: [u64; 6],
+    teams: [u64; 2],
+    current_player: Team,
+    //The rooks that can still castle
+    castling_rooks: u64,
+    en_passant: Option<Square>,
+}
+
+//The check and pin state of the side to move, see BitBoard::get_check_info
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CheckInfo {
+    king: Option<Square>,
+    checkers: u64,
+    pinned: u64,
+    king_danger: u64,
+}
+
+impl CheckInfo {
+    pub fn get_checkers(&self) -> u64 {
+        self.checkers
+    }
+
+    pub fn get_pinned(&self) -> u64 {
+        self.pinned
+    }
+
+    pub fn get_king_danger(&self) -> u64 {
+        self.king_danger
+    }
+
+    pub fn is_check(&self) -> bool {
+        self.checkers != 0
+    }
+}
+
+impl BitBoard {
+    pub fn from_board(board: &Board) -> Self {
+        let mut bitboard = BitBoard {
+            pieces: [0; 6],
+            teams: [0; 2],
+            current_player: board.get_current_team(),
+            castling_rooks: 0,
+            en_passant: board.get_en_passant_square(),
+        };
+
+        for square in Square::all() {
+            if let Some(piece) = board.get_piece(square) {
+                bitboard.pieces[piece_index(piece.get_piece_type())] |= bit(square);
+                bitboard.teams[team_index(piece.get_team())] |= bit(square);
+            }
+        }
+
+        for (_, king_square, rook_square) in CASTLING_RIGHTS {
+            if board.has_castling_right(king_square, rook_square) {
+                bitboard.castling_rooks |= bit(rook_square);
+            }
+        }
+
+        bitboard
+    }
+
+    pub fn get_current_team(&self) -> Team {
+        self.current_player
+    }
+
+    pub fn get_pieces(&self, piece_type: PieceType, team: Team) -> u64 {
+        self.pieces[piece_index(piece_type)] & self.teams[team_index(team)]
+    }
+
+    pub fn get_occupied(&self) -> u64 {
+        self.teams[0] | self.teams[1]
+    }
+
+    pub fn get_piece(&self, square: Square) -> Option<(PieceType, Team)> {
+        let team = if self.teams[0] & bit(square) != 0 {
+            Team::White
+        } else if self.teams[1] & bit(square) != 0 {
+            Team::Black
+        } else {
+            return None;
+        };
+
+        PIECE_TYPES
+            .iter()
+            .find(|piece_type| self.pieces[piece_index(**piece_type)] & bit(square) != 0)
+            .map(|piece_type| (*piece_type, team))
+    }
+
+    pub fn is_square_attacked(&self, square: Square, by_team: Team) -> bool {
+        self.attackers_of(square, by_team) != 0
+    }
+
+    //The pieces of a team that could capture on a square, whatever stands on it.
+    //Pawns only attack diagonally, so a pawn push never counts as an attack
+    pub fn attackers_of(&self, square: Square, by_team: Team) -> u64 {
+        let occupied = self.get_occupied();
+        let queens = self.get_pieces(PieceType::Queen, by_team);
+
+        knight_attacks(square) & self.get_pieces(PieceType::Knight, by_team)
+            | king_attacks(square) & self.get_pieces(PieceType::King, by_team)
+            | pawn_attacks(by_team.opponent(), square) & self.get_pieces(PieceType::Pawn, by_team)
+            | rook_attacks(square, occupied) & (self.get_pieces(PieceType::Rook, by_team) | queens)
+            | bishop_attacks(square, occupied)
+                & (self.get_pieces(PieceType::Bishop, by_team) | queens)
+    }
+
+    //How many pieces of a team attack each square, by square index
+    pub fn attack_counts(&self, team: Team) -> [u8; 64] {
+        let occupied = self.get_occupied();
+        let mut counts = [0; 64];
+
+        for piece_type in PIECE_TYPES {
+            for from in squares(self.get_pieces(piece_type, team)) {
+                for square in squares(piece_attacks(piece_type, team, from, occupied)) {
+                    counts[square.index()] += 1;
+                }
+            }
+        }
+
+        counts
+    }
+
+    pub fn is_check(&self) -> bool {
+        squares(self.get_pieces(PieceType::King, self.current_player))
+            .any(|king| self.is_square_attacked(king, self.current_player.opponent()))
+    }
+
+    //Finds the pieces checking the king of the side to move, the pieces pinned to it
+    //and the squares it can't step on, so moves can be checked without playing them
+    pub fn get_check_info(&self) -> CheckInfo {
+        let team = self.current_player;
+        let enemy_team = team.opponent();
+        let own = self.teams[team_index(team)];
+        let occupied = self.get_occupied();
+
+        let king = match squares(self.get_pieces(PieceType::King, team)).next() {
+            Some(king) => king,
+            None => return CheckInfo::default(),
+        };
+
+        let queens = self.get_pieces(PieceType::Queen, enemy_team);
+        let straight = self.get_pieces(PieceType::Rook, enemy_team) | queens;
+        let diagonal = self.get_pieces(PieceType::Bishop, enemy_team) | queens;
+
+        let checkers = knight_attacks(king) & self.get_pieces(PieceType::Knight, enemy_team)
+            | pawn_attacks(team, king) & self.get_pieces(PieceType::Pawn, enemy_team)
+            | rook_attacks(king, occupied) & straight
+            | bishop_attacks(king, occupied) & diagonal;
+
+        //A sliding piece lined up with the king pins the only piece standing in between
+        let snipers = rook_attacks(king, 0) & straight | bishop_attacks(king, 0) & diagonal;
+        let mut pinned = 0;
+
+        for sniper in squares(snipers) {
+            let blockers = squares_between(king, sniper) & occupied;
+
+            if blockers.count_ones() == 1 {
+                pinned |= blockers & own;
+            }
+        }
+
+        //The king is taken off the board so it can't hide from a slider behind itself
+        let without_king = occupied & !bit(king);
+        let mut king_danger = 0;
+
+        for piece_type in PIECE_TYPES {
+            for square in squares(self.get_pieces(piece_type, enemy_team)) {
+                king_danger |= piece_attacks(piece_type, enemy_team, square, without_king);
+            }
+        }
+
+        CheckInfo {
+            king: Some(king),
+            checkers,
+            pinned,
+            king_danger,
+        }
+    }
+
+    //Whether a pseudo-legal move of the side to move keeps its king out of check
+    pub fn is_legal(&self, mv: Move, check_info: &CheckInfo) -> bool {
+        let king = match check_info.king {
+            Some(king) => king,
+            None => return true,
+        };
+
+        //Castling moves are only generated when the king's path isn't attacked
+        if mv.from == king {
+            return mv.castle || check_info.king_danger & bit(mv.to) == 0;
+        }
+
+        //Only the king can get out of a double check
+        if check_info.checkers.count_ones() > 1 {
+            return false;
+        }
+
+        //En passant removes two pieces from the same rank, which can uncover the king
+        //in ways a pin doesn't describe, so it is played out on a copy
+        if mv.en_passant {
+            let mut next = *self;
+            next.make_move(mv);
+            return !next.is_square_attacked(king, self.current_player.opponent());
+        }
+
+        if let Some(checker) = squares(check_info.checkers).next() {
+            if (bit(checker) | squares_between(king, checker)) & bit(mv.to) == 0 {
+                return false;
+            }
+        }
+
+        check_info.pinned & bit(mv.from) == 0 || line_through(king, mv.from) & bit(mv.to) != 0
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let check_info = self.get_check_info();
+
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|mv| self.is_legal(*mv, &check_info))
+            .collect()
+    }
+
+    fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let mut result = Vec::new();
+        let team = self.current_player;
+        let own = self.teams[team_index(team)];
+        let enemy = self.teams[team_index(team.opponent())];
+        let occupied = own | enemy;
+
+        for from in squares(self.get_pieces(PieceType::Pawn, team)) {
+            self.push_pawn_moves(&mut result, from, occupied, enemy);
+        }
+
+        for piece_type in &PIECE_TYPES[1..] {
+            for from in squares(self.get_pieces(*piece_type, team)) {
+                for to in squares(piece_attacks(*piece_type, team, from, occupied) & !own) {
+                    result.push(Move::basic(from, to));
+                }
+            }
+        }
+
+        self.push_castling_moves(&mut result, occupied);
+        result
+    }
+
+    fn push_pawn_moves(&self, result: &mut Vec<Move>, from: Square, occupied: u64, enemy: u64) {
+        let (direction, start_row, promotion_row) = match self.current_player {
+            Team::White => (-1, 6, 0),
+            Team::Black => (1, 1, 7),
+        };
+
+        let mut push = |to: Square| {
+            if to.row() == promotion_row {
+                for piece_type in PROMOTION_PIECES {
+                    result.push(Move::promotion(from, to, piece_type));
+                }
+            } else {
+                result.push(Move::basic(from, to));
+            }
+        };
+
+        if let Some(to) = from
+            .offset(direction, 0)
+            .filter(|to| occupied & bit(*to) == 0)
+        {
+            push(to);
+
+            if let Some(to) = from
+                .offset(direction * 2, 0)
+                .filter(|to| from.row() == start_row && occupied & bit(*to) == 0)
+            {
+                push(to);
+            }
+        }
+
+        for to in squares(pawn_attacks(self.current_player, from) & enemy) {
+            push(to);
+        }
+
+        if let Some(target) = self.en_passant {
+            if pawn_attacks(self.current_player, from) & bit(target) != 0 {
+                result.push(Move::en_passant(from, target));
+            }
+        }
+    }
+
+    fn push_castling_moves(&self, result: &mut Vec<Move>, occupied: u64) {
+        let team = self.current_player;
+        let enemy_team = team.opponent();
+
+        for rook in squares(self.castling_rooks & self.teams[team_index(team)]) {
+            let king = match team {
+                Team::White => Square::E1,
+                Team::Black => Square::E8,
+            };
+
+            let side = if rook > king { 1 } else { -1 };
+            let between = squares_between(king, rook);
+            let king_path = [Some(king), king.offset(0, side), king.offset(0, side * 2)];
+
+            if occupied & between != 0 {
+                continue;
+            }
+
+            if king_path
+                .iter()
+                .any(|square| square.is_none_or(|s| self.is_square_attacked(s, enemy_team)))
+            {
+                continue;
+            }
+
+            if let Some(to) = king_path[2] {
+                result.push(Move::castle(king, to));
+            }
+        }
+    }
+
+    //Plays a move from the legal move list, without checking it
+    pub fn make_move(&mut self, mv: Move) {
+        let team = self.current_player;
+        let (us, them) = (team_index(team), team_index(team.opponent()));
+
+        let moved = match self.get_piece(mv.from) {
+            Some((piece_type, _)) => piece_type,
+            None => return,
+        };
+
+        let captured_square = if mv.en_passant {
+            Square::from_row_col(mv.from.row(), mv.to.col()).unwrap_or(mv.to)
+        } else {
+            mv.to
+        };
+
+        for pieces in self.pieces.iter_mut() {
+            *pieces &= !bit(captured_square);
+        }
+        self.teams[them] &= !bit(captured_square);
+
+        let placed = mv.promotion_piece.unwrap_or(moved);
+        self.pieces[piece_index(moved)] &= !bit(mv.from);
+        self.pieces[piece_index(placed)] |= bit(mv.to);
+        self.teams[us] = (self.teams[us] & !bit(mv.from)) | bit(mv.to);
+
+        if let Some((rook_from, rook_to)) = mv.castling_rook_squares() {
+            let rook_move = bit(rook_from) | bit(rook_to);
+            self.pieces[piece_index(PieceType::Rook)] ^= rook_move;
+            self.teams[us] ^= rook_move;
+        }
+
+        //Moving the king gives up both castling rights, moving or capturing a rook only its own
+        if moved == PieceType::King {
+            self.castling_rooks &= !self.teams[us];
+        }
+        self.castling_rooks &= !(bit(mv.from) | bit(mv.to));
+
+        self.en_passant = None;
+        if moved == PieceType::Pawn && mv.from.row().abs_diff(mv.to.row()) == 2 {
+            self.en_passant = mv.from.offset((mv.to.row() - mv.from.row()) / 2, 0);
+        }
+
+        self.current_player = team.opponent();
+    }
+
+    pub fn perft(&self, depth: u8) -> u64 {
+        match depth {
+            0 => 1,
+            1 => self.legal_moves().len() as u64,
+            _ => self
+                .legal_moves()
+                .iter()
+                .map(|mv| {
+                    let mut next = *self;
+                    next.make_move(*mv);
+                    next.perft(depth - 1)
+                })
+                .sum(),
+        }
+    }
+}
+
+//The squares strictly between two squares on the same rank, file or diagonal
+pub fn squares_between(a: Square, b: Square) -> u64 {
+    let (rows, cols) = (a.row().abs_diff(b.row()), a.col().abs_diff(b.col()));
+
+    if a == b {
+        0
+    } else if rows == 0 || cols == 0 {
+        rook_attacks(a, bit(b)) & rook_attacks(b, bit(a))
+    } else if rows == cols {
+        bishop_attacks(a, bit(b)) & bishop_attacks(b, bit(a))
+    } else {
+        0
+    }
+}
+
+//Every square of the rank, file or diagonal through two squares, from edge to edge
+pub fn line_through(a: Square, b: Square) -> u64 {
+    let (rows, cols) = (a.row().abs_diff(b.row()), a.col().abs_diff(b.col()));
+
+    if a == b {
+        0
+    } else if rows == 0 || cols == 0 {
+        rook_attacks(a, 0) & rook_attacks(b, 0) | bit(a) | bit(b)
+    } else if rows == cols {
+        bishop_attacks(a, 0) & bishop_attacks(b, 0) | bit(a) | bit(b)
+    } else {
+        0
+    }
+}
@@ -1,105 +1,494 @@
-use std::{ops::Index, time::Instant};
+use std::{str::FromStr, time::Instant};
 
 use serde::Serialize;
 use tracing::info;
 
 use super::{
-    moves::Move,
+    bitboard::{BitBoard, CheckInfo},
+    fen::{FenError, FenErrorKind, FenField},
+    moves::{IllegalMove, Move, MoveOutcome},
+    outcome::{DrawReason, GameOutcome},
     piece::{Piece, PieceType, Team},
+    square::Square,
+    validation::PositionError,
+    zobrist::{piece_key, turn_key},
 };
 
 #[derive(Clone, Serialize)]
 pub struct Board {
-    fen: String,
+    starting_fen: String,
     pieces: Vec<Option<Piece>>,
     available_moves: Vec<Move>,
     current_player: Team,
     turn: u16,
-    en_passant: bool,
-    last_moved_piece: usize,
+    halfmove_clock: u16,
+    en_passant: Option<Square>,
     check: bool,
-    mate: bool,
+    outcome: GameOutcome,
+    claimable_draw: Option<DrawReason>,
+    repetitions: u8,
+    #[serde(skip)]
+    hash: u64,
+    #[serde(skip)]
+    position_keys: Vec<u64>,
+    #[serde(skip)]
+    history: Vec<MoveRecord>,
+    #[serde(skip)]
+    redo_moves: Vec<Move>,
 }
 
-//Starting board:
-//rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR
+//Everything make_move changes that can't be recomputed from the move itself
+#[derive(Clone)]
+struct MoveRecord {
+    mv: Move,
+    moved_piece: Piece,
+    captured_piece: Option<Piece>,
+    castled_rook: Option<Piece>,
+    en_passant: Option<Square>,
+    hash: u64,
+    turn: u16,
+    halfmove_clock: u16,
+}
+
+//(castling character, king square, rook square)
+pub(crate) const CASTLING_RIGHTS: [(char, Square, Square); 4] = [
+    ('K', Square::E1, Square::H1),
+    ('Q', Square::E1, Square::A1),
+    ('k', Square::E8, Square::H8),
+    ('q', Square::E8, Square::A8),
+];
+
+impl FromStr for Board {
+    type Err = FenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Board::try_from_fen(s)
+    }
+}
+
+//Far beyond the longest game the seventy-five move rule allows
+const MAX_FULLMOVE_NUMBER: u16 = 10_000;
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 impl Board {
     pub fn from_fen(fen_string: &str) -> Self {
+        Board::try_from_fen(fen_string).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_from_fen(fen_string: &str) -> Result<Self, FenError> {
         let mut board = Board {
-            fen: String::from(fen_string),
+            starting_fen: String::from(fen_string.trim()),
             pieces: vec![None; 64],
             available_moves: Vec::new(),
             current_player: Team::White,
             turn: 1,
-            en_passant: false,
-            last_moved_piece: 0,
+            halfmove_clock: 0,
+            en_passant: None,
             check: false,
-            mate: false,
+            outcome: GameOutcome::Ongoing,
+            claimable_draw: None,
+            repetitions: 1,
+            hash: 0,
+            position_keys: Vec::new(),
+            history: Vec::new(),
+            redo_moves: Vec::new(),
         };
 
-        board.initialize();
+        board.initialize()?;
+
+        let problems = board.validate();
+        if !problems.is_empty() {
+            return Err(FenError::new(
+                FenField::Placement,
+                1,
+                FenErrorKind::InvalidPosition(problems),
+            ));
+        }
+
         board.generate_moves(false);
-        board
+        Ok(board)
     }
 
-    fn initialize(&mut self) {
-        let mut fen_chars: Vec<char> = vec!['.'; 64];
+    fn initialize(&mut self) -> Result<(), FenError> {
+        let fen = self.starting_fen.clone();
 
-        let mut count = 0;
-        for ch in self.fen.chars() {
-            if ch == '/' {
-                continue;
+        //(1-based column where the field starts, field). Columns count characters,
+        //while the fields are sliced by byte offset
+        let mut fields: Vec<(usize, &str)> = Vec::new();
+        let mut field_start: Option<(usize, usize)> = None;
+
+        for (column, (i, ch)) in fen.char_indices().enumerate() {
+            match (ch.is_whitespace(), field_start) {
+                (true, Some((start, start_column))) => {
+                    fields.push((start_column + 1, &fen[start..i]));
+                    field_start = None;
+                }
+                (false, None) => field_start = Some((i, column)),
+                _ => {}
+            }
+        }
+
+        if let Some((start, start_column)) = field_start {
+            fields.push((start_column + 1, &fen[start..]));
+        }
+
+        if fields.len() > 6 {
+            return Err(FenError::new(
+                FenField::FullmoveNumber,
+                fields[6].0,
+                FenErrorKind::TooManyFields(fields.len()),
+            ));
+        }
+
+        //Missing fields fall back to the values of the starting position
+        let end_column = fen.chars().count() + 1;
+        let field = |i: usize, default: &'static str| match fields.get(i) {
+            Some(field) => *field,
+            None => (end_column, default),
+        };
+
+        self.initialize_pieces(field(0, ""))?;
+
+        let (column, side_to_move) = field(1, "w");
+        self.current_player = match side_to_move {
+            "w" => Team::White,
+            "b" => Team::Black,
+            _ => {
+                return Err(FenError::new(
+                    FenField::SideToMove,
+                    column,
+                    FenErrorKind::InvalidSideToMove(String::from(side_to_move)),
+                ))
             }
+        };
+
+        self.initialize_castling(field(2, "KQkq"))?;
+        self.initialize_en_passant(field(3, "-"))?;
+
+        self.halfmove_clock = Board::parse_fen_number(field(4, "0"), FenField::HalfmoveClock)?;
+        self.turn = Board::parse_fen_number(field(5, "1"), FenField::FullmoveNumber)?;
+
+        if self.turn == 0 || self.turn > MAX_FULLMOVE_NUMBER {
+            return Err(FenError::new(
+                FenField::FullmoveNumber,
+                field(5, "1").0,
+                FenErrorKind::InvalidNumber(self.turn.to_string()),
+            ));
+        }
+
+        self.hash = self.compute_hash();
+
+        Ok(())
+    }
+
+    fn parse_fen_number((column, number): (usize, &str), field: FenField) -> Result<u16, FenError> {
+        number.parse().map_err(|_| {
+            FenError::new(
+                field,
+                column,
+                FenErrorKind::InvalidNumber(String::from(number)),
+            )
+        })
+    }
+
+    fn initialize_pieces(&mut self, (column, placement): (usize, &str)) -> Result<(), FenError> {
+        let ranks: Vec<&str> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(FenError::new(
+                FenField::Placement,
+                column,
+                FenErrorKind::InvalidRankCount(ranks.len()),
+            ));
+        }
+
+        let mut rank_column = column;
 
-            let digit = ch.to_digit(10);
+        for (row, rank) in ranks.iter().enumerate() {
+            let mut col = 0;
+            let mut previous_digit: Option<char> = None;
 
-            match digit {
-                Some(x) => {
-                    for _ in 0..x {
-                        fen_chars[count] = '.';
-                        count = count + 1;
+            for (i, ch) in rank.chars().enumerate() {
+                let piece = match ch {
+                    '1'..='8' => {
+                        //A run of empty squares is written as one digit, never as "44"
+                        if let Some(previous) = previous_digit {
+                            return Err(FenError::new(
+                                FenField::Placement,
+                                rank_column + i,
+                                FenErrorKind::ConsecutiveDigits(format!("{}{}", previous, ch)),
+                            ));
+                        }
+
+                        previous_digit = Some(ch);
+                        col += ch.to_digit(10).unwrap() as usize;
+                        None
+                    }
+                    'p' => Some((PieceType::Pawn, Team::Black)),
+                    'P' => Some((PieceType::Pawn, Team::White)),
+                    'r' => Some((PieceType::Rook, Team::Black)),
+                    'R' => Some((PieceType::Rook, Team::White)),
+                    'n' => Some((PieceType::Knight, Team::Black)),
+                    'N' => Some((PieceType::Knight, Team::White)),
+                    'b' => Some((PieceType::Bishop, Team::Black)),
+                    'B' => Some((PieceType::Bishop, Team::White)),
+                    'q' => Some((PieceType::Queen, Team::Black)),
+                    'Q' => Some((PieceType::Queen, Team::White)),
+                    'k' => Some((PieceType::King, Team::Black)),
+                    'K' => Some((PieceType::King, Team::White)),
+                    _ => {
+                        return Err(FenError::new(
+                            FenField::Placement,
+                            rank_column + i,
+                            FenErrorKind::InvalidPiece(ch),
+                        ))
+                    }
+                };
+
+                if let Some((piece_type, team)) = piece {
+                    if let Some(square) = Square::from_row_col(row as i32, col as i32) {
+                        self.pieces[square.index()] = Some(Piece::new(piece_type, team, square));
                     }
+
+                    previous_digit = None;
+                    col += 1;
                 }
-                _ => {
-                    fen_chars[count] = ch;
-                    count = count + 1;
+
+                if col > 8 {
+                    return Err(FenError::new(
+                        FenField::Placement,
+                        rank_column + i,
+                        FenErrorKind::InvalidRankLength {
+                            rank: 8 - row,
+                            length: col,
+                        },
+                    ));
                 }
             }
+
+            if col != 8 {
+                return Err(FenError::new(
+                    FenField::Placement,
+                    rank_column + rank.chars().count(),
+                    FenErrorKind::InvalidRankLength {
+                        rank: 8 - row,
+                        length: col,
+                    },
+                ));
+            }
+
+            rank_column += rank.chars().count() + 1;
         }
 
-        for (i, ch) in fen_chars.iter().enumerate() {
-            self.pieces[i] = match ch {
-                'p' => Option::from(Piece::new(PieceType::Pawn, Team::Black, i)),
-                'P' => Option::from(Piece::new(PieceType::Pawn, Team::White, i)),
-                'r' => Option::from(Piece::new(PieceType::Rook, Team::Black, i)),
-                'R' => Option::from(Piece::new(PieceType::Rook, Team::White, i)),
-                'n' => Option::from(Piece::new(PieceType::Knight, Team::Black, i)),
-                'N' => Option::from(Piece::new(PieceType::Knight, Team::White, i)),
-                'b' => Option::from(Piece::new(PieceType::Bishop, Team::Black, i)),
-                'B' => Option::from(Piece::new(PieceType::Bishop, Team::White, i)),
-                'q' => Option::from(Piece::new(PieceType::Queen, Team::Black, i)),
-                'Q' => Option::from(Piece::new(PieceType::Queen, Team::White, i)),
-                'k' => Option::from(Piece::new(PieceType::King, Team::Black, i)),
-                'K' => Option::from(Piece::new(PieceType::King, Team::White, i)),
-                '.' => None,
-                _ => panic!("Invalid FEN character: {}", ch),
+        //Pawns away from their starting rank can't double step anymore
+        for piece in self.pieces.iter_mut().flatten() {
+            if piece.get_piece_type() != PieceType::Pawn {
+                continue;
             }
+
+            let start_row = match piece.get_team() {
+                Team::White => 6,
+                Team::Black => 1,
+            };
+
+            piece.moved(piece.get_index().row() != start_row);
         }
+
+        Ok(())
+    }
+
+    fn initialize_castling(&mut self, (column, castling): (usize, &str)) -> Result<(), FenError> {
+        if castling != "-" {
+            for (i, ch) in castling.chars().enumerate() {
+                if !CASTLING_RIGHTS.iter().any(|(right, _, _)| *right == ch)
+                    || castling.chars().take(i).any(|previous| previous == ch)
+                {
+                    return Err(FenError::new(
+                        FenField::Castling,
+                        column + i,
+                        FenErrorKind::InvalidCastling(ch),
+                    ));
+                }
+            }
+        }
+
+        //Castling is tracked through the moved flags, so kings and rooks count as moved
+        //unless the castling field says they can still castle
+        for piece in self.pieces.iter_mut().flatten() {
+            if let PieceType::King | PieceType::Rook = piece.get_piece_type() {
+                piece.moved(true);
+            }
+        }
+
+        for (right, king_square, rook_square) in CASTLING_RIGHTS {
+            if !castling.contains(right) {
+                continue;
+            }
+
+            let team = if right.is_uppercase() {
+                Team::White
+            } else {
+                Team::Black
+            };
+
+            let is_piece = |piece: &Option<Piece>, piece_type: PieceType| match piece {
+                Some(p) => p.get_piece_type() == piece_type && p.get_team() == team,
+                None => false,
+            };
+
+            //A right without its king and rook in place couldn't be written back by to_fen
+            if !is_piece(self.get_piece(king_square), PieceType::King)
+                || !is_piece(self.get_piece(rook_square), PieceType::Rook)
+            {
+                let i = castling.chars().position(|ch| ch == right).unwrap_or(0);
+
+                return Err(FenError::new(
+                    FenField::Castling,
+                    column + i,
+                    FenErrorKind::InvalidCastling(right),
+                ));
+            }
+
+            for square in [king_square, rook_square] {
+                if let Some(piece) = self.pieces[square.index()].as_mut() {
+                    piece.moved(false);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn initialize_en_passant(&mut self, (column, en_passant): (usize, &str)) -> Result<(), FenError> {
+        self.en_passant = None;
+
+        if en_passant == "-" {
+            return Ok(());
+        }
+
+        //The target square is behind a pawn of the side that just moved
+        let target_row = match self.current_player {
+            Team::White => 2,
+            Team::Black => 5,
+        };
+
+        let target_index = match en_passant.parse::<Square>() {
+            Ok(square) if square.row() == target_row => square,
+            _ => {
+                return Err(FenError::new(
+                    FenField::EnPassant,
+                    column,
+                    FenErrorKind::InvalidEnPassant(String::from(en_passant)),
+                ))
+            }
+        };
+
+        self.en_passant = Some(target_index);
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Vec<PositionError> {
+        let mut problems: Vec<PositionError> = Vec::new();
+
+        for team in [Team::White, Team::Black] {
+            let pieces: Vec<&Piece> = self
+                .pieces
+                .iter()
+                .flatten()
+                .filter(|p| p.get_team() == team)
+                .collect();
+
+            let count = |piece_type: PieceType| {
+                pieces
+                    .iter()
+                    .filter(|p| p.get_piece_type() == piece_type)
+                    .count()
+            };
+
+            match count(PieceType::King) {
+                0 => problems.push(PositionError::MissingKing(team)),
+                1 => {}
+                _ => problems.push(PositionError::TooManyKings(team)),
+            }
+
+            if count(PieceType::Pawn) > 8 {
+                problems.push(PositionError::TooManyPawns(team));
+            }
+
+            if pieces.len() > 16 {
+                problems.push(PositionError::TooManyPieces(team));
+            }
+        }
+
+        for piece in self.pieces.iter().flatten() {
+            let row = piece.get_index().row();
+
+            if piece.get_piece_type() == PieceType::Pawn && (row == 0 || row == 7) {
+                problems.push(PositionError::PawnOnBackRank(piece.get_index()));
+            }
+        }
+
+        //The side that just moved can't have left its own king in check
+        let opponent = self.current_player.opponent();
+        let opponent_kings: Vec<&Piece> = self
+            .pieces
+            .iter()
+            .flatten()
+            .filter(|p| p.get_piece_type() == PieceType::King && p.get_team() == opponent)
+            .collect();
+
+        if let [king] = opponent_kings[..] {
+            if self.is_attacked(king.get_index(), self.current_player) {
+                problems.push(PositionError::OpponentInCheck(opponent));
+            }
+        }
+
+        //En passant needs an enemy pawn that just double stepped over two empty squares
+        if let Some(target_index) = self.en_passant {
+            let direction = match self.current_player {
+                Team::White => 1,
+                Team::Black => -1,
+            };
+
+            let piece_at = |square: Option<Square>| square.and_then(|s| *self.get_piece(s));
+
+            let has_pawn = match piece_at(target_index.offset(direction, 0)) {
+                Some(p) => p.get_piece_type() == PieceType::Pawn && p.get_team() == opponent,
+                None => false,
+            };
+
+            if !has_pawn
+                || self.get_piece(target_index).is_some()
+                || piece_at(target_index.offset(-direction, 0)).is_some()
+            {
+                problems.push(PositionError::InvalidEnPassant(target_index));
+            }
+        }
+
+        problems
     }
 
     pub fn reset(&mut self) {
         tracing::info!("Reseting board...");
 
-        self.fen = String::from(self.fen.clone());
         self.pieces = vec![None; 64];
         self.available_moves = Vec::new();
         self.current_player = Team::White;
         self.turn = 1;
-        self.last_moved_piece = 0;
-        self.en_passant = false;
+        self.halfmove_clock = 0;
+        self.en_passant = None;
+        self.check = false;
+        self.outcome = GameOutcome::Ongoing;
+        self.claimable_draw = None;
+        self.repetitions = 1;
+        self.position_keys.clear();
+        self.history.clear();
+        self.redo_moves.clear();
 
-        self.initialize();
+        self.initialize()
+            .expect("The starting FEN was already validated when the board was created");
 
         self.generate_moves(false);
 
@@ -110,41 +499,23 @@ impl Board {
         let mv_gen_time = Instant::now();
         self.available_moves.clear();
 
-        for i in 0..self.pieces.len() {
-            if let Some(piece) = self.get_piece(i) {
+        let bitboard = BitBoard::from_board(self);
+
+        for square in Square::all() {
+            if let Some(piece) = self.get_piece(square) {
                 if piece.get_team() != self.current_player {
                     continue;
                 }
 
-                let available_moves = match piece.get_piece_type() {
-                    PieceType::Bishop => Move::bishop(piece, self),
-                    PieceType::King => Move::king(piece, self),
-                    PieceType::Knight => Move::knight(piece, self),
-                    PieceType::Pawn => Move::pawn(piece, self),
-                    PieceType::Queen => Move::queen(piece, self),
-                    PieceType::Rook => Move::rook(piece, self),
-                    _ => Vec::new(),
-                };
-
-                for am in available_moves {
+                for am in Move::piece_moves(piece, self, &bitboard) {
                     self.available_moves.push(am);
                 }
             }
         }
 
-        let mut filtered_moves: Vec<Move> = Vec::new();
-
-        for am in self.available_moves.to_vec() {
-            if !simulation && Move::possible_mate(am, self.clone()) {
-                continue;
-            }
-
-            filtered_moves.push(am);
-        }
-
-        self.available_moves.clone_from(&filtered_moves);
-
         if !simulation {
+            let check_info = self.retain_legal_moves_with(&bitboard);
+
             info!(
                 "{:?}: {} legal moves found in {:.2?}",
                 self.get_current_team(),
@@ -152,161 +523,615 @@ impl Board {
                 mv_gen_time.elapsed(),
             );
 
-            if self.available_moves.len() == 0 {
-                self.mate = true;
-            }
-
-            self.check = self.get_is_check();
+            self.check = check_info.is_check();
+            self.repetitions = self.count_repetitions();
+            self.outcome = self.compute_outcome();
+            self.claimable_draw = self.compute_claimable_draw();
         }
     }
 
-    pub fn make_move(&mut self, mv: Move, simulation: bool) {
-        //Double checking if the move provided is actually one of the available moves
-        if !self
-            .available_moves
-            .iter()
-            .any(|x| x.from == mv.from && x.to == mv.to && x.en_passant == mv.en_passant)
-        {
-            return;
+    //Drops the moves that would leave the king in check, using the check and pin
+    //state of the position instead of playing each move out
+    pub(crate) fn retain_legal_moves(&mut self) {
+        let bitboard = BitBoard::from_board(self);
+        self.retain_legal_moves_with(&bitboard);
+    }
+
+    fn retain_legal_moves_with(&mut self, bitboard: &BitBoard) -> CheckInfo {
+        let check_info = bitboard.get_check_info();
+
+        self.available_moves
+            .retain(|mv| bitboard.is_legal(*mv, &check_info));
+
+        check_info
+    }
+
+    //Check and game status in the outcome are only computed outside of simulations
+    pub fn make_move(&mut self, mv: Move, simulation: bool) -> Result<MoveOutcome, IllegalMove> {
+        if !simulation && self.outcome.is_over() {
+            return Err(IllegalMove::GameOver(self.outcome));
         }
 
+        self.replay_move(mv, simulation)
+    }
+
+    //Plays the move even if the game is already over, for recorded games that carried on
+    //after a dead position or the seventy-five move rule
+    pub(crate) fn replay_move(
+        &mut self,
+        mv: Move,
+        simulation: bool,
+    ) -> Result<MoveOutcome, IllegalMove> {
+        let mv = self.find_legal_move(mv)?;
         let from_piece = self.get_piece(mv.from).unwrap();
 
-        //Double checking if the move provided is actually from the current player
-        if from_piece.get_team() != self.current_player {
-            return;
-        }
+        //The pawn captured en passant sits beside the capturing pawn, on the target square's file
+        let captured_square = if mv.en_passant {
+            mv.to.offset(mv.from.row() - mv.to.row(), 0).unwrap_or(mv.to)
+        } else {
+            mv.to
+        };
+
+        let captured_piece = *self.get_piece(captured_square);
+        let captured = captured_piece.map(|p| p.get_piece_type());
+
+        let mut record = MoveRecord {
+            mv,
+            moved_piece: from_piece,
+            captured_piece,
+            castled_rook: None,
+            en_passant: self.en_passant,
+            hash: self.hash,
+            turn: self.turn,
+            halfmove_clock: self.halfmove_clock,
+        };
+
+        //SAN needs the move played on a copy, so it is only worked out when it gets logged
+        if !simulation && tracing::enabled!(tracing::Level::INFO) {
+            let notation = match self.to_san(mv) {
+                Ok(san) => san,
+                Err(_) => format!("{} to {}", mv.from, mv.to),
+            };
 
-        if !simulation {
             tracing::info!(
-                "MOVE -> Turn {}, {:?} | {} to {}",
+                "MOVE -> Turn {}, {:?} | {}",
                 self.turn,
                 self.current_player,
-                Board::get_coordinates_from_index(mv.from),
-                Board::get_coordinates_from_index(mv.to)
+                notation
             );
         }
 
-        let mut new_piece = Piece::new(from_piece.get_piece_type(), from_piece.get_team(), mv.to);
-        new_piece.moved(true);
+        if !simulation {
+            self.position_keys.push(self.repetition_key());
+        }
+
+        //Castling rights and en passant are hashed out before the move and back in after it,
+        //the pieces are updated square by square as they move
+        let mut hash = self.hash ^ self.castling_hash() ^ self.en_passant_hash() ^ turn_key();
+
+        //The halfmove clock only counts moves without captures or pawn advances
+        if from_piece.get_piece_type() == PieceType::Pawn || captured.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+
+        //A promoting pawn is replaced by the chosen piece
+        let piece_type = mv.promotion_piece.unwrap_or(from_piece.get_piece_type());
 
-        self.pieces[mv.to] = Some(new_piece);
-        self.pieces[mv.from] = None;
+        let mut new_piece = Piece::new(piece_type, from_piece.get_team(), mv.to);
+        new_piece.moved(true);
 
-        //Capture the last moved piece if the current move is an en passant
-        if mv.en_passant {
-            self.pieces[self.last_moved_piece] = None;
+        if let Some(captured_piece) = captured_piece {
+            hash ^= piece_key(
+                captured_piece.get_piece_type(),
+                captured_piece.get_team(),
+                captured_square,
+            );
         }
 
-        self.en_passant = false;
+        hash ^= piece_key(from_piece.get_piece_type(), from_piece.get_team(), mv.from);
+        hash ^= piece_key(piece_type, from_piece.get_team(), mv.to);
+
+        self.pieces[captured_square.index()] = None;
+        self.pieces[mv.to.index()] = Some(new_piece);
+        self.pieces[mv.from.index()] = None;
+
+        //Move the rook to the other side of the king when castling
+        if let Some((rook_from, rook_to)) = mv.castling_rook_squares() {
+            if let Some(rook) = *self.get_piece(rook_from) {
+                let mut new_rook = Piece::new(rook.get_piece_type(), rook.get_team(), rook_to);
+                new_rook.moved(true);
+
+                self.pieces[rook_to.index()] = Some(new_rook);
+                self.pieces[rook_from.index()] = None;
+                record.castled_rook = Some(rook);
 
-        //Check if an en passant is possible on the next turn
-        if from_piece.get_piece_type() == PieceType::Pawn {
-            if i8::abs(mv.to as i8 - mv.from as i8) == 16 {
-                self.en_passant = true;
+                hash ^= piece_key(rook.get_piece_type(), rook.get_team(), rook_from);
+                hash ^= piece_key(rook.get_piece_type(), rook.get_team(), rook_to);
             }
         }
 
-        self.last_moved_piece = mv.to;
+        //A pawn double step leaves the square it skipped over as the en passant target
+        self.en_passant = None;
+
+        if from_piece.get_piece_type() == PieceType::Pawn && mv.from.row().abs_diff(mv.to.row()) == 2 {
+            self.en_passant = mv.from.offset((mv.to.row() - mv.from.row()) / 2, 0);
+        }
 
         self.current_player = match self.current_player {
             Team::White => Team::Black,
             Team::Black => {
-                self.turn = self.turn + 1;
+                self.turn = self.turn.saturating_add(1);
                 Team::White
             }
         };
 
+        self.hash = hash ^ self.castling_hash() ^ self.en_passant_hash();
+        debug_assert_eq!(self.hash, self.compute_hash());
+
+        self.history.push(record);
+
+        if !simulation {
+            self.redo_moves.clear();
+        }
+
         self.generate_moves(simulation);
+
+        Ok(MoveOutcome {
+            mv,
+            captured,
+            castle: mv.castle,
+            promotion: mv.promotion_piece,
+            check: self.check,
+            status: self.outcome,
+        })
     }
 
-    pub fn get_is_check(&mut self) -> bool {
-        let mut cloned_board = self.clone();
-        cloned_board.current_player = match cloned_board.current_player {
-            Team::Black => Team::White,
-            Team::White => Team::Black,
+    //Restores the position from before the last move, returning the move that was taken back
+    pub fn unmake_move(&mut self, simulation: bool) -> Option<Move> {
+        let record = self.history.pop()?;
+        let mv = record.mv;
+
+        if !simulation {
+            tracing::info!(
+                "UNDO -> Turn {}, {:?} | {} to {}",
+                record.turn,
+                record.moved_piece.get_team(),
+                mv.from,
+                mv.to
+            );
+        }
+
+        self.pieces[mv.to.index()] = None;
+        self.pieces[mv.from.index()] = Some(record.moved_piece);
+
+        if let Some(captured_piece) = record.captured_piece {
+            self.pieces[captured_piece.get_index().index()] = Some(captured_piece);
+        }
+
+        if let (Some(rook), Some((_, rook_to))) = (record.castled_rook, mv.castling_rook_squares()) {
+            self.pieces[rook_to.index()] = None;
+            self.pieces[rook.get_index().index()] = Some(rook);
+        }
+
+        self.current_player = record.moved_piece.get_team();
+        self.en_passant = record.en_passant;
+        self.turn = record.turn;
+        self.halfmove_clock = record.halfmove_clock;
+        self.hash = record.hash;
+        debug_assert_eq!(self.hash, self.compute_hash());
+
+        if !simulation {
+            self.position_keys.pop();
+        }
+
+        self.generate_moves(simulation);
+
+        if !simulation {
+            self.redo_moves.push(mv);
+        }
+
+        Some(mv)
+    }
+
+    //Counts the positions reached after every sequence of depth legal moves, to check
+    //the move generator against published node counts
+    pub fn perft(&self, depth: u8) -> u64 {
+        match depth {
+            0 => 1,
+            1 => self.available_moves.len() as u64,
+            _ => self
+                .available_moves
+                .iter()
+                .map(|mv| self.play_perft_move(*mv).perft(depth - 1))
+                .sum(),
+        }
+    }
+
+    //The perft node count below each legal move
+    pub fn divide(&self, depth: u8) -> Vec<(Move, u64)> {
+        self.available_moves
+            .iter()
+            .map(|mv| {
+                let nodes = if depth <= 1 {
+                    1
+                } else {
+                    self.play_perft_move(*mv).perft(depth - 1)
+                };
+
+                (*mv, nodes)
+            })
+            .collect()
+    }
+
+    //Plays the move on a copy without the game rules bookkeeping of a real move,
+    //keeping only the legal replies
+    fn play_perft_move(&self, mv: Move) -> Board {
+        let mut board = self.clone();
+        board
+            .make_move(mv, true)
+            .expect("Perft only plays moves from the legal move list");
+
+        board.retain_legal_moves();
+        board
+    }
+
+    //Replays the last move taken back by unmake_move, if no other move was made since
+    pub fn redo_move(&mut self) -> Option<MoveOutcome> {
+        let mv = self.redo_moves.pop()?;
+
+        //make_move forgets the undone moves, so they are put back after replaying this one
+        let redo_moves = std::mem::take(&mut self.redo_moves);
+        let outcome = self.make_move(mv, false);
+        self.redo_moves = redo_moves;
+
+        //A move that can't be replayed, like after a draw was claimed, stays redoable
+        if outcome.is_err() {
+            self.redo_moves.push(mv);
+        }
+
+        outcome.ok()
+    }
+
+    pub fn get_starting_fen(&self) -> &str {
+        &self.starting_fen
+    }
+
+    pub fn get_move_history(&self) -> Vec<Move> {
+        self.history.iter().map(|record| record.mv).collect()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_moves.is_empty()
+    }
+
+    //Resolves the move against the legal moves so the castle and en passant flags
+    //don't have to be provided, explaining why it's illegal otherwise
+    pub(crate) fn find_legal_move(&self, mv: Move) -> Result<Move, IllegalMove> {
+        let piece = match self.get_piece(mv.from) {
+            Some(piece) => piece,
+            None => return Err(IllegalMove::NoPieceOnSquare(mv.from)),
         };
 
-        cloned_board.generate_moves(true);
+        if piece.get_team() != self.current_player {
+            return Err(IllegalMove::WrongSideToMove(piece.get_team()));
+        }
+
+        let same_squares = |x: &&Move| x.from == mv.from && x.to == mv.to;
 
-        cloned_board
+        if let Some(legal_move) = self
             .available_moves
             .iter()
-            .any(|mv| cloned_board.get_piece_type_by_index(mv.to) == PieceType::King)
+            .filter(same_squares)
+            .find(|x| x.promotion_piece == mv.promotion_piece)
+        {
+            return Ok(*legal_move);
+        }
+
+        if mv.promotion_piece.is_none() && self.available_moves.iter().any(|x| same_squares(&x)) {
+            return Err(IllegalMove::MissingPromotionChoice);
+        }
+
+        //The piece could make the move if it weren't for its own king's safety
+        if Move::piece_moves(piece, self, &BitBoard::from_board(self))
+            .iter()
+            .filter(same_squares)
+            .any(|x| x.promotion_piece == mv.promotion_piece)
+        {
+            return Err(IllegalMove::LeavesKingInCheck);
+        }
+
+        Err(IllegalMove::CannotReachSquare {
+            from: mv.from,
+            to: mv.to,
+        })
     }
 
-    pub fn en_passant_possible(&self) -> bool {
-        self.en_passant
+    fn compute_outcome(&self) -> GameOutcome {
+        //No legal moves: it's a checkmate if the king is attacked, otherwise a stalemate
+        if self.available_moves.is_empty() {
+            if self.check {
+                return GameOutcome::Checkmate {
+                    winner: self.current_player.opponent(),
+                };
+            }
+
+            return GameOutcome::Draw {
+                reason: DrawReason::Stalemate,
+            };
+        }
+
+        //Dead positions where neither side can ever checkmate end the game immediately
+        if self.is_insufficient_material() {
+            return GameOutcome::Draw {
+                reason: DrawReason::InsufficientMaterial,
+            };
+        }
+
+        //75 moves by each side without a capture or pawn move end the game automatically
+        if self.halfmove_clock >= 150 {
+            return GameOutcome::Draw {
+                reason: DrawReason::SeventyFiveMoveRule,
+            };
+        }
+
+        if self.repetitions >= 5 {
+            return GameOutcome::Draw {
+                reason: DrawReason::FivefoldRepetition,
+            };
+        }
+
+        GameOutcome::Ongoing
     }
 
-    pub fn get_pieces(&self) -> Vec<Option<Piece>> {
-        self.pieces.clone()
+    //Draws that only end the game if a player claims them
+    fn compute_claimable_draw(&self) -> Option<DrawReason> {
+        if self.outcome.is_over() {
+            return None;
+        }
+
+        if self.repetitions >= 3 {
+            return Some(DrawReason::ThreefoldRepetition);
+        }
+
+        if self.halfmove_clock >= 100 {
+            return Some(DrawReason::FiftyMoveRule);
+        }
+
+        None
     }
 
-    pub fn get_piece(&self, index: usize) -> &Option<Piece> {
-        self.pieces.index(index)
+    pub fn is_insufficient_material(&self) -> bool {
+        self.has_insufficient_material(Team::White) && self.has_insufficient_material(Team::Black)
     }
 
-    pub fn get_piece_type_by_index(&self, index: usize) -> PieceType {
-        if let Some(p) = self.get_piece(index) {
-            return p.get_piece_type();
+    //A side without mating material can't win even on time, so a flag fall against
+    //the other side is a draw
+    pub fn has_mating_material(&self, team: Team) -> bool {
+        !self.has_insufficient_material(team)
+    }
+
+    //Checks if the team can't checkmate by any sequence of legal moves,
+    //even with the help of the opponent
+    fn has_insufficient_material(&self, team: Team) -> bool {
+        let (pieces, enemy_pieces): (Vec<&Piece>, Vec<&Piece>) = self
+            .pieces
+            .iter()
+            .flatten()
+            .filter(|p| p.get_piece_type() != PieceType::King)
+            .partition(|p| p.get_team() == team);
+
+        let has = |pieces: &Vec<&Piece>, piece_types: &[PieceType]| {
+            pieces
+                .iter()
+                .any(|p| piece_types.contains(&p.get_piece_type()))
+        };
+
+        //Pawns can promote, and rooks and queens can always force a mate
+        if has(
+            &pieces,
+            &[PieceType::Pawn, PieceType::Rook, PieceType::Queen],
+        ) {
+            return false;
         }
 
-        PieceType::Empty
+        //A lone knight can only mate if the enemy has pieces to block its own king in,
+        //queens can't do that as they would give check themselves
+        if has(&pieces, &[PieceType::Knight]) {
+            return pieces.len() == 1
+                && enemy_pieces
+                    .iter()
+                    .all(|p| p.get_piece_type() == PieceType::Queen);
+        }
+
+        //Bishops all on squares of the same color can only mate with the help of enemy
+        //pawns, knights or bishops on the other color
+        if has(&pieces, &[PieceType::Bishop]) {
+            let square_colors: Vec<bool> = self
+                .pieces
+                .iter()
+                .flatten()
+                .filter(|p| p.get_piece_type() == PieceType::Bishop)
+                .map(|p| p.get_index().is_light())
+                .collect();
+
+            return square_colors.iter().all(|c| *c == square_colors[0])
+                && !has(&enemy_pieces, &[PieceType::Pawn, PieceType::Knight]);
+        }
+
+        true
+    }
+
+    fn count_repetitions(&self) -> u8 {
+        let key = self.repetition_key();
+
+        let previous = self.position_keys.iter().filter(|k| **k == key).count();
+
+        (previous + 1).min(u8::MAX as usize) as u8
     }
 
-    pub fn get_last_move(&self) -> usize {
-        self.last_moved_piece
+    pub fn get_repetitions(&self) -> u8 {
+        self.repetitions
     }
 
-    pub fn get_piece_moves(&self, index: usize) -> Vec<Move> {
-        self.available_moves
+    pub fn get_claimable_draw(&self) -> Option<DrawReason> {
+        self.claimable_draw
+    }
+
+    pub fn claim_draw(&mut self) -> Option<GameOutcome> {
+        let reason = self.claimable_draw?;
+
+        tracing::info!("{:?} claimed a draw by {:?}", self.current_player, reason);
+
+        self.outcome = GameOutcome::Draw { reason };
+        self.claimable_draw = None;
+
+        Some(self.outcome)
+    }
+
+    pub fn get_halfmove_clock(&self) -> u16 {
+        self.halfmove_clock
+    }
+
+    pub fn get_outcome(&self) -> GameOutcome {
+        self.outcome
+    }
+
+    pub fn is_check(&self) -> bool {
+        self.check
+    }
+
+    pub fn get_is_check(&self) -> bool {
+        BitBoard::from_board(self).is_check()
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut placement = String::new();
+
+        for row in 0..8 {
+            let mut empty_squares = 0;
+
+            for square in Square::all().filter(|square| square.row() == row) {
+                match self.get_piece(square) {
+                    Some(piece) => {
+                        if empty_squares > 0 {
+                            placement.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+
+                        placement.push(piece.get_fen_char());
+                    }
+                    None => empty_squares += 1,
+                }
+            }
+
+            if empty_squares > 0 {
+                placement.push_str(&empty_squares.to_string());
+            }
+
+            if row < 7 {
+                placement.push('/');
+            }
+        }
+
+        let side_to_move = match self.current_player {
+            Team::White => "w",
+            Team::Black => "b",
+        };
+
+        let mut castling: String = CASTLING_RIGHTS
             .iter()
-            .filter(|&mov| mov.from == index)
-            .cloned()
-            .collect()
+            .filter(|(_, king_square, rook_square)| {
+                self.has_castling_right(*king_square, *rook_square)
+            })
+            .map(|(right, _, _)| *right)
+            .collect();
+
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        //The en passant target is the square the double stepping pawn skipped over
+        let en_passant = match self.en_passant {
+            Some(target) => target.to_string(),
+            None => String::from("-"),
+        };
+
+        format!(
+            "{} {} {} {} {} {}",
+            placement, side_to_move, castling, en_passant, self.halfmove_clock, self.turn
+        )
     }
 
-    pub fn get_current_team(&self) -> Team {
-        self.current_player
+    //Castling rights are kept as the moved flags of the king and the rook
+    pub(crate) fn has_castling_right(&self, king_square: Square, rook_square: Square) -> bool {
+        match (self.get_piece(king_square), self.get_piece(rook_square)) {
+            (Some(king), Some(rook)) => {
+                king.get_piece_type() == PieceType::King
+                    && rook.get_piece_type() == PieceType::Rook
+                    && king.get_team() == rook.get_team()
+                    && !king.has_moved()
+                    && !rook.has_moved()
+            }
+            _ => false,
+        }
     }
 
-    pub fn get_possible_moves(&self) -> &Vec<Move> {
-        &self.available_moves
+    pub fn get_hash(&self) -> u64 {
+        self.hash
     }
 
-    pub fn get_index(row: usize, col: usize) -> usize {
-        row * 8 + col
+    pub fn get_en_passant_square(&self) -> Option<Square> {
+        self.en_passant
     }
 
-    pub fn get_row_col(index: i32) -> (i32, i32) {
-        let row = index / 8;
-        let col = index % 8;
+    pub fn get_pieces(&self) -> Vec<Option<Piece>> {
+        self.pieces.clone()
+    }
+
+    pub fn get_piece(&self, square: Square) -> &Option<Piece> {
+        &self.pieces[square.index()]
+    }
 
-        (row, col)
+    pub fn get_piece_type(&self, square: Square) -> PieceType {
+        if let Some(p) = self.get_piece(square) {
+            return p.get_piece_type();
+        }
+
+        PieceType::Empty
     }
 
-    pub fn get_coordinates_from_index(index: usize) -> String {
-        let (row, col) = Board::get_row_col(index as i32);
+    pub fn get_piece_moves(&self, square: Square) -> Vec<Move> {
+        self.available_moves
+            .iter()
+            .filter(|&mov| mov.from == square)
+            .cloned()
+            .collect()
+    }
 
-        let col_str = (8 - row).to_string();
-        let row_str = ('A' as u8 + col as u8) as char;
+    pub fn get_turn(&self) -> u16 {
+        self.turn
+    }
 
-        let result = format!("{}{}", row_str, col_str);
+    pub fn get_current_team(&self) -> Team {
+        self.current_player
+    }
 
-        result
+    pub fn get_possible_moves(&self) -> &Vec<Move> {
+        &self.available_moves
     }
 
     pub fn print(&self) {
-        for i in 0..64 {
-            let piece = self.get_piece(i);
+        for square in Square::all() {
+            let piece = self.get_piece(square);
             match piece {
                 Some(x) => print!("{:?}->{:?} ", x.get_team(), x.get_piece_type()),
                 _ => print!("{:?} ", PieceType::Empty),
             }
 
-            if (i + 1) % 8 == 0 {
-                println!("");
+            if square.col() == 7 {
+                println!();
             }
         }
     }
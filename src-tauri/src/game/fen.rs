@@ -0,0 +1,104 @@
+use std::{error::Error, fmt};
+
+use super::validation::PositionError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FenField {
+    Placement,
+    SideToMove,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenErrorKind {
+    InvalidPiece(char),
+    ConsecutiveDigits(String),
+    InvalidRankLength { rank: usize, length: usize },
+    InvalidRankCount(usize),
+    InvalidSideToMove(String),
+    InvalidCastling(char),
+    InvalidEnPassant(String),
+    InvalidNumber(String),
+    TooManyFields(usize),
+    InvalidPosition(Vec<PositionError>),
+}
+
+//Column is the 1-based character position in the FEN string where the problem was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct FenError {
+    pub field: FenField,
+    pub column: usize,
+    pub kind: FenErrorKind,
+}
+
+impl FenError {
+    pub fn new(field: FenField, column: usize, kind: FenErrorKind) -> Self {
+        FenError {
+            field,
+            column,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FenField::Placement => "piece placement",
+            FenField::SideToMove => "side to move",
+            FenField::Castling => "castling availability",
+            FenField::EnPassant => "en passant target square",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for FenErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenErrorKind::InvalidPiece(ch) => write!(f, "invalid piece character '{}'", ch),
+            FenErrorKind::ConsecutiveDigits(digits) => {
+                write!(f, "consecutive digits '{}' in a rank", digits)
+            }
+            FenErrorKind::InvalidRankLength { rank, length } => {
+                write!(f, "rank {} describes {} squares instead of 8", rank, length)
+            }
+            FenErrorKind::InvalidRankCount(count) => {
+                write!(f, "found {} ranks instead of 8", count)
+            }
+            FenErrorKind::InvalidSideToMove(side) => {
+                write!(f, "expected 'w' or 'b', found '{}'", side)
+            }
+            FenErrorKind::InvalidCastling(ch) => write!(f, "invalid castling character '{}'", ch),
+            FenErrorKind::InvalidEnPassant(square) => {
+                write!(f, "'{}' is not a valid en passant square", square)
+            }
+            FenErrorKind::InvalidNumber(number) => write!(f, "'{}' is not a valid number", number),
+            FenErrorKind::TooManyFields(count) => {
+                write!(f, "found {} fields instead of at most 6", count)
+            }
+            FenErrorKind::InvalidPosition(problems) => {
+                let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+                write!(f, "impossible position ({})", problems.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid FEN {} at column {}: {}",
+            self.field, self.column, self.kind
+        )
+    }
+}
+
+impl Error for FenError {}
@@ -1,3 +1,13 @@
+pub mod attacks;
+pub mod bitboard;
 pub mod board;
+pub mod fen;
 pub mod moves;
+pub mod outcome;
+pub mod pgn;
 pub mod piece;
+pub mod san;
+pub mod square;
+pub mod uci;
+pub mod validation;
+pub mod zobrist;
@@ -1,58 +1,145 @@
+use std::{error::Error, fmt};
+
 use serde::{Deserialize, Serialize};
 
 use super::{
+    bitboard::BitBoard,
     board::Board,
+    outcome::GameOutcome,
     piece::{Piece, PieceType, Team},
+    square::Square,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Move {
-    pub from: usize,
-    pub to: usize,
+    pub from: Square,
+    pub to: Square,
     pub en_passant: bool,
+    pub castle: bool,
     pub promotion_piece: Option<PieceType>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MoveOutcome {
+    pub mv: Move,
+    pub captured: Option<PieceType>,
+    pub castle: bool,
+    pub promotion: Option<PieceType>,
+    pub check: bool,
+    pub status: GameOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum IllegalMove {
+    NoPieceOnSquare(Square),
+    WrongSideToMove(Team),
+    CannotReachSquare { from: Square, to: Square },
+    LeavesKingInCheck,
+    MissingPromotionChoice,
+    GameOver(GameOutcome),
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IllegalMove::NoPieceOnSquare(square) => write!(f, "There is no piece on {}", square),
+            IllegalMove::WrongSideToMove(team) => write!(f, "It's not {:?}'s turn", team),
+            IllegalMove::CannotReachSquare { from, to } => {
+                write!(f, "The piece on {} can't move to {}", from, to)
+            }
+            IllegalMove::LeavesKingInCheck => write!(f, "The move would leave the king in check"),
+            IllegalMove::MissingPromotionChoice => {
+                write!(f, "A promotion piece must be chosen")
+            }
+            IllegalMove::GameOver(outcome) => write!(f, "The game is already over ({:?})", outcome),
+        }
+    }
+}
+
+impl Error for IllegalMove {}
+
 impl Move {
-    pub fn basic(from: usize, to: usize) -> Self {
+    pub fn basic(from: Square, to: Square) -> Self {
         Move {
-            from: from,
-            to: to,
+            from,
+            to,
             en_passant: false,
+            castle: false,
             promotion_piece: None,
         }
     }
 
-    pub fn en_passant(from: usize, to: usize) -> Self {
+    pub fn en_passant(from: Square, to: Square) -> Self {
         Move {
-            from: from,
-            to: to,
+            from,
+            to,
             en_passant: true,
+            castle: false,
             promotion_piece: None,
         }
     }
 
-    pub fn promotion(from: usize, to: usize, promotion_piece: PieceType) -> Self {
+    pub fn promotion(from: Square, to: Square, promotion_piece: PieceType) -> Self {
         Move {
-            from: from,
-            to: to,
+            from,
+            to,
             en_passant: false,
+            castle: false,
             promotion_piece: Some(promotion_piece),
         }
     }
+
+    pub fn castle(from: Square, to: Square) -> Self {
+        Move {
+            from,
+            to,
+            en_passant: false,
+            castle: true,
+            promotion_piece: None,
+        }
+    }
 }
 
 impl Move {
+    //Where the rook starts and ends when the move is a castle
+    pub fn castling_rook_squares(&self) -> Option<(Square, Square)> {
+        if !self.castle {
+            return None;
+        }
+
+        let (rook_col, rook_to_col) = if self.to > self.from {
+            (3, 1)
+        } else {
+            (-4, -1)
+        };
+
+        Some((self.from.offset(0, rook_col)?, self.from.offset(0, rook_to_col)?))
+    }
+}
+
+impl Move {
+    //Pseudo-legal moves for the piece, without checking if they leave the king in check.
+    //bitboard must be built from board, and is only read for the castling attack checks
+    pub fn piece_moves(piece: &Piece, board: &Board, bitboard: &BitBoard) -> Vec<Move> {
+        match piece.get_piece_type() {
+            PieceType::Bishop => Move::bishop(piece, board),
+            PieceType::King => Move::king(piece, board, bitboard),
+            PieceType::Knight => Move::knight(piece, board),
+            PieceType::Pawn => Move::pawn(piece, board),
+            PieceType::Queen => Move::queen(piece, board),
+            PieceType::Rook => Move::rook(piece, board),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn bishop(piece: &Piece, board: &Board) -> Vec<Move> {
         let mut result: Vec<Move> = Vec::new();
         let index = piece.get_index();
-        let (row, col) = Board::get_row_col(index as i32);
+        let (row, col) = (index.row(), index.col());
 
         //Left-up diagonal
         let (mut t_row, mut t_col) = (row - 1, col - 1);
-        while !Move::out_of_bounds(t_row, t_col) {
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
-
+        while let Some(target_index) = Square::from_row_col(t_row, t_col) {
             t_row -= 1;
             t_col -= 1;
 
@@ -70,9 +157,7 @@ impl Move {
 
         //Right-up diagonal
         let (mut t_row, mut t_col) = (row - 1, col + 1);
-        while !Move::out_of_bounds(t_row, t_col) {
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
-
+        while let Some(target_index) = Square::from_row_col(t_row, t_col) {
             t_row -= 1;
             t_col += 1;
 
@@ -90,9 +175,7 @@ impl Move {
 
         //Left-down diagonal
         let (mut t_row, mut t_col) = (row + 1, col - 1);
-        while !Move::out_of_bounds(t_row, t_col) {
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
-
+        while let Some(target_index) = Square::from_row_col(t_row, t_col) {
             t_row += 1;
             t_col -= 1;
 
@@ -110,9 +193,7 @@ impl Move {
 
         //Right-down diagonal
         let (mut t_row, mut t_col) = (row + 1, col + 1);
-        while !Move::out_of_bounds(t_row, t_col) {
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
-
+        while let Some(target_index) = Square::from_row_col(t_row, t_col) {
             t_row += 1;
             t_col += 1;
 
@@ -131,10 +212,10 @@ impl Move {
         result
     }
 
-    pub fn king(piece: &Piece, board: &Board) -> Vec<Move> {
+    pub fn king(piece: &Piece, board: &Board, bitboard: &BitBoard) -> Vec<Move> {
         let mut result: Vec<Move> = Vec::new();
         let index = piece.get_index();
-        let (row, col) = Board::get_row_col(index as i32);
+        let (row, col) = (index.row(), index.col());
 
         let targets = vec![
             (row - 1, col - 1),
@@ -148,11 +229,10 @@ impl Move {
         ];
 
         for (t_row, t_col) in targets.into_iter() {
-            if Move::out_of_bounds(t_row, t_col) {
-                continue;
-            }
-
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
+            let target_index = match Square::from_row_col(t_row, t_col) {
+                Some(square) => square,
+                None => continue,
+            };
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -163,13 +243,72 @@ impl Move {
             result.push(Move::basic(index, target_index));
         }
 
+        for mv in Move::castling(piece, board, bitboard) {
+            result.push(mv);
+        }
+
+        result
+    }
+
+    pub fn castling(piece: &Piece, board: &Board, bitboard: &BitBoard) -> Vec<Move> {
+        let mut result: Vec<Move> = Vec::new();
+        let index = piece.get_index();
+
+        let (king_square, enemy_team) = match piece.get_team() {
+            Team::White => (Square::E1, Team::Black),
+            Team::Black => (Square::E8, Team::White),
+        };
+
+        //The king can't castle after moving, and can't castle out of check
+        if piece.has_moved()
+            || index != king_square
+            || bitboard.is_square_attacked(index, enemy_team)
+        {
+            return result;
+        }
+
+        //(rook col, cols that must be empty, cols the king crosses or lands on),
+        //all relative to the king
+        let sides = vec![(3, vec![1, 2], vec![1, 2]), (-4, vec![-1, -2, -3], vec![-1, -2])];
+
+        for (rook_col, empty_cols, king_path_cols) in sides {
+            let squares = |cols: &Vec<i32>| -> Vec<Square> {
+                cols.iter()
+                    .filter_map(|col| index.offset(0, *col))
+                    .collect()
+            };
+
+            let (empty_squares, king_path) = (squares(&empty_cols), squares(&king_path_cols));
+
+            match index.offset(0, rook_col).and_then(|s| *board.get_piece(s)) {
+                Some(rook)
+                    if rook.get_piece_type() == PieceType::Rook
+                        && rook.get_team() == piece.get_team()
+                        && !rook.has_moved() => {}
+                _ => continue,
+            }
+
+            if empty_squares.iter().any(|&s| board.get_piece(s).is_some()) {
+                continue;
+            }
+
+            if king_path
+                .iter()
+                .any(|&s| bitboard.is_square_attacked(s, enemy_team))
+            {
+                continue;
+            }
+
+            result.push(Move::castle(index, king_path[1]));
+        }
+
         result
     }
 
     pub fn knight(piece: &Piece, board: &Board) -> Vec<Move> {
         let mut result: Vec<Move> = Vec::new();
         let index = piece.get_index();
-        let (row, col) = Board::get_row_col(index as i32);
+        let (row, col) = (index.row(), index.col());
 
         let targets = vec![
             (row + 2, col + 1),
@@ -183,11 +322,10 @@ impl Move {
         ];
 
         for (t_row, t_col) in targets.into_iter() {
-            if Move::out_of_bounds(t_row, t_col) {
-                continue;
-            }
-
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
+            let target_index = match Square::from_row_col(t_row, t_col) {
+                Some(square) => square,
+                None => continue,
+            };
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -205,7 +343,7 @@ impl Move {
         let mut result: Vec<Move> = Vec::new();
 
         let index = piece.get_index();
-        let (row, col) = Board::get_row_col(index as i32);
+        let (row, col) = (index.row(), index.col());
 
         //if it's a white pawn, we want to subtract 1 from the row and vice-versa
         let team_modifier = match piece.get_team() {
@@ -221,18 +359,17 @@ impl Move {
         }
 
         for (t_row, t_col) in targets {
-            if Move::out_of_bounds(t_row, t_col) {
-                continue;
-            }
-
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
+            let target_index = match Square::from_row_col(t_row, t_col) {
+                Some(square) => square,
+                None => continue,
+            };
 
             //pawns can't capture normally, so break out of the loop if there's a piece in front of it
             if let Some(_target_piece) = board.get_piece(target_index) {
                 break;
             }
 
-            result.push(Move::basic(index, target_index));
+            Move::push_pawn_move(&mut result, index, target_index);
         }
 
         //Check diagonals for captures
@@ -242,42 +379,45 @@ impl Move {
         ];
 
         for (t_row, t_col) in capture_targets {
-            if Move::out_of_bounds(t_row, t_col) {
-                continue;
-            }
-
-            let target_index = Board::get_index(t_row as usize, t_col as usize);
+            let target_index = match Square::from_row_col(t_row, t_col) {
+                Some(square) => square,
+                None => continue,
+            };
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
                     continue;
                 }
 
-                result.push(Move::basic(index, target_index));
+                Move::push_pawn_move(&mut result, index, target_index);
             }
         }
 
-        //En passant
-        if board.en_passant_possible() {
-            let ep_index = board.get_last_move();
-            let (ep_row, ep_col) = Board::get_row_col(ep_index as i32);
-
-            if ep_index == index
-                || row != ep_row
-                || board.get_piece(ep_index).unwrap().get_team() == piece.get_team()
-            {
-                return result;
+        //En passant: the target square must be diagonally in front of the pawn
+        if let Some(ep_index) = board.get_en_passant_square() {
+            if ep_index.row() == row + team_modifier && i32::abs(ep_index.col() - col) == 1 {
+                result.push(Move::en_passant(index, ep_index));
             }
+        }
 
-            let diff = ep_col - col;
+        result
+    }
 
-            result.push(Move::en_passant(
-                index,
-                Board::get_index((row + team_modifier) as usize, (col + diff) as usize),
-            ));
+    //Pawns reaching the last rank must promote, so one move is generated per promotion choice
+    fn push_pawn_move(result: &mut Vec<Move>, from: Square, to: Square) {
+        if to.row() != 0 && to.row() != 7 {
+            result.push(Move::basic(from, to));
+            return;
         }
 
-        result
+        for promotion_piece in [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ] {
+            result.push(Move::promotion(from, to, promotion_piece));
+        }
     }
 
     pub fn queen(piece: &Piece, board: &Board) -> Vec<Move> {
@@ -298,12 +438,11 @@ impl Move {
         let mut result: Vec<Move> = Vec::new();
 
         let index = piece.get_index();
-        let (row, col) = Board::get_row_col(index as i32);
+        let (row, col) = (index.row(), index.col());
 
         //Rows above
-        for t_row in (0..(row)).rev() {
+        for target_index in ((0..(row)).rev()).filter_map(|t_row| Square::from_row_col(t_row, col)) {
             let mut stop = false;
-            let target_index = Board::get_index(t_row as usize, col as usize);
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -321,9 +460,8 @@ impl Move {
         }
 
         //Rows below
-        for t_row in (row + 1)..8 {
+        for target_index in ((row + 1)..8).filter_map(|t_row| Square::from_row_col(t_row, col)) {
             let mut stop = false;
-            let target_index = Board::get_index(t_row as usize, col as usize);
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -341,9 +479,8 @@ impl Move {
         }
 
         //Cols to the left
-        for t_col in (0..(col)).rev() {
+        for target_index in ((0..(col)).rev()).filter_map(|t_col| Square::from_row_col(row, t_col)) {
             let mut stop = false;
-            let target_index = Board::get_index(row as usize, t_col as usize);
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -361,9 +498,8 @@ impl Move {
         }
 
         //Cols to the right
-        for t_col in (col + 1)..8 {
+        for target_index in ((col + 1)..8).filter_map(|t_col| Square::from_row_col(row, t_col)) {
             let mut stop = false;
-            let target_index = Board::get_index(row as usize, t_col as usize);
 
             if let Some(target_piece) = board.get_piece(target_index) {
                 if target_piece.get_team() == piece.get_team() {
@@ -381,22 +517,4 @@ impl Move {
         }
         result
     }
-
-    pub fn possible_mate(mv: Move, mut cloned_board: Board) -> bool {
-        cloned_board.make_move(mv, true);
-
-        //If the king would be in check after the move,
-        //return true so the move can be filtered out
-        for mv in cloned_board.get_possible_moves() {
-            if cloned_board.get_piece_type_by_index(mv.to) == PieceType::King {
-                return true;
-            }
-        }
-
-        false
-    }
-
-    fn out_of_bounds(row: i32, col: i32) -> bool {
-        row < 0 || row > 7 || col < 0 || col > 7
-    }
 }
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use super::piece::Team;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(tag = "status")]
+pub enum GameOutcome {
+    Ongoing,
+    Checkmate { winner: Team },
+    Draw { reason: DrawReason },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum DrawReason {
+    Stalemate,
+    InsufficientMaterial,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+}
+
+impl GameOutcome {
+    pub fn is_over(&self) -> bool {
+        *self != GameOutcome::Ongoing
+    }
+
+    pub fn get_winner(&self) -> Option<Team> {
+        match self {
+            GameOutcome::Checkmate { winner } => Some(*winner),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,824 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt,
+    io::BufRead,
+    iter::Peekable,
+    str::Chars,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use super::{
+    board::{Board, STARTING_FEN},
+    fen::FenError,
+    moves::{IllegalMove, Move},
+    outcome::GameOutcome,
+    piece::Team,
+    san::SanError,
+};
+
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+//PGN export lines should not be longer than 80 characters
+const MAX_LINE_LENGTH: usize = 80;
+
+const RESULT_TOKENS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PgnGame {
+    pub tags: Vec<(String, String)>,
+    //Comments written before the first move
+    pub comments: Vec<String>,
+    pub moves: Vec<PgnMove>,
+    pub result: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PgnMove {
+    pub san: String,
+    pub mv: Move,
+    pub nags: Vec<u8>,
+    pub comments: Vec<String>,
+    //Alternatives to this move, each starting from the position before it
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgnErrorKind {
+    InvalidTag,
+    UnterminatedTag,
+    UnterminatedComment,
+    UnterminatedVariation,
+    InvalidNag(String),
+    UnexpectedToken(String),
+    InvalidFen(FenError),
+    InvalidMove(SanError),
+    IllegalMove(IllegalMove),
+    ReadFailed(String),
+}
+
+//Line and column are 1-based and point at the start of the offending token
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind,
+}
+
+impl PgnError {
+    pub fn new(line: usize, column: usize, kind: PgnErrorKind) -> Self {
+        PgnError { line, column, kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    Open,
+    Close,
+    Result(String),
+    San(String),
+}
+
+impl Token {
+    //The text the token starts with, for error messages
+    fn to_text(&self) -> String {
+        match self {
+            Token::Tag(name, _) => format!("[{}", name),
+            Token::Comment(_) => String::from("{"),
+            Token::Nag(nag) => format!("${}", nag),
+            Token::Open => String::from("("),
+            Token::Close => String::from(")"),
+            Token::Result(text) | Token::San(text) => text.clone(),
+        }
+    }
+}
+
+struct PgnLexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    pending: VecDeque<(Token, usize, usize)>,
+}
+
+impl<'a> PgnLexer<'a> {
+    fn new(text: &'a str, first_line: usize) -> Self {
+        PgnLexer {
+            chars: text.chars().peekable(),
+            line: first_line,
+            column: 1,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|ch| ch.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(ch) = self.bump() {
+            if ch == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<(Token, usize, usize)>, PgnError> {
+        if let Some(token) = self.pending.pop_front() {
+            return Ok(Some(token));
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            let (line, column) = (self.line, self.column);
+            let error = |kind: PgnErrorKind| PgnError::new(line, column, kind);
+
+            let ch = match self.chars.peek() {
+                Some(ch) => *ch,
+                None => return Ok(None),
+            };
+
+            let token = match ch {
+                //Lines starting with '%' are escaped and meant for other programs
+                '%' if column == 1 => {
+                    self.skip_line();
+                    continue;
+                }
+                ';' => {
+                    self.bump();
+                    let mut comment = String::new();
+                    while let Some(ch) = self.chars.peek().copied().filter(|ch| *ch != '\n') {
+                        comment.push(ch);
+                        self.bump();
+                    }
+                    Token::Comment(comment.trim().to_string())
+                }
+                '{' => {
+                    self.bump();
+                    let mut comment = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('}') => break,
+                            Some(ch) => comment.push(ch),
+                            None => return Err(error(PgnErrorKind::UnterminatedComment)),
+                        }
+                    }
+                    Token::Comment(comment.trim().to_string())
+                }
+                '[' => self.read_tag().map_err(error)?,
+                '(' => {
+                    self.bump();
+                    Token::Open
+                }
+                ')' => {
+                    self.bump();
+                    Token::Close
+                }
+                '$' => {
+                    self.bump();
+                    let digits = self.read_symbol();
+                    match digits.parse::<u8>() {
+                        Ok(nag) => Token::Nag(nag),
+                        Err(_) => return Err(error(PgnErrorKind::InvalidNag(digits))),
+                    }
+                }
+                _ => {
+                    let symbol = self.read_symbol();
+                    if symbol.is_empty() {
+                        self.bump();
+                        return Err(error(PgnErrorKind::UnexpectedToken(ch.to_string())));
+                    }
+
+                    match self.split_symbol(&symbol, line, column) {
+                        Some((token, column)) => return Ok(Some((token, line, column))),
+                        None => continue,
+                    }
+                }
+            };
+
+            return Ok(Some((token, line, column)));
+        }
+    }
+
+    fn read_symbol(&mut self) -> String {
+        let mut symbol = String::new();
+
+        while let Some(ch) = self.chars.peek().copied() {
+            if ch.is_whitespace() || "{}()[];$%\"".contains(ch) {
+                break;
+            }
+
+            symbol.push(ch);
+            self.bump();
+        }
+
+        symbol
+    }
+
+    //Separates move numbers and suffix annotations like "12.e4!?" from the move itself,
+    //returning the move and its column. Returns None when the symbol was only a move number
+    fn split_symbol(&mut self, symbol: &str, line: usize, column: usize) -> Option<(Token, usize)> {
+        if RESULT_TOKENS.contains(&symbol) {
+            return Some((Token::Result(String::from(symbol)), column));
+        }
+
+        let san = if symbol.starts_with("0-0") {
+            symbol
+        } else {
+            symbol
+                .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                .trim_start_matches('.')
+        };
+
+        if san.is_empty() {
+            return None;
+        }
+
+        let move_column = column + symbol.len() - san.len();
+        let annotation_start = san.trim_end_matches(['!', '?']).len();
+        let (san, annotation) = san.split_at(annotation_start);
+
+        let nag = match annotation {
+            "!" => Some(1),
+            "?" => Some(2),
+            "!!" => Some(3),
+            "??" => Some(4),
+            "!?" => Some(5),
+            "?!" => Some(6),
+            _ => None,
+        };
+
+        if let Some(nag) = nag {
+            self.pending
+                .push_back((Token::Nag(nag), line, move_column + annotation_start));
+        }
+
+        Some((Token::San(String::from(san)), move_column))
+    }
+
+    fn read_tag(&mut self) -> Result<Token, PgnErrorKind> {
+        self.bump();
+        self.skip_whitespace();
+
+        let mut name = String::new();
+        while let Some(ch) = self.chars.peek().copied() {
+            if !(ch.is_ascii_alphanumeric() || ch == '_') {
+                break;
+            }
+            name.push(ch);
+            self.bump();
+        }
+
+        self.skip_whitespace();
+
+        if name.is_empty() || self.bump() != Some('"') {
+            return Err(PgnErrorKind::InvalidTag);
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some(ch) => value.push(ch),
+                    None => return Err(PgnErrorKind::UnterminatedTag),
+                },
+                Some('"') => break,
+                Some('\n') | None => return Err(PgnErrorKind::UnterminatedTag),
+                Some(ch) => value.push(ch),
+            }
+        }
+
+        self.skip_whitespace();
+
+        match self.bump() {
+            Some(']') => Ok(Token::Tag(name, value)),
+            Some(_) => Err(PgnErrorKind::InvalidTag),
+            None => Err(PgnErrorKind::UnterminatedTag),
+        }
+    }
+}
+
+struct PgnParser<'a> {
+    lexer: PgnLexer<'a>,
+    peeked: Option<(Token, usize, usize)>,
+}
+
+impl<'a> PgnParser<'a> {
+    fn new(text: &'a str, first_line: usize) -> Self {
+        PgnParser {
+            lexer: PgnLexer::new(text, first_line),
+            peeked: None,
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<(Token, usize, usize)>, PgnError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&(Token, usize, usize)>, PgnError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+
+        Ok(self.peeked.as_ref())
+    }
+
+    //Reads one game. Without headers_only the movetext is replayed on a board, which is
+    //returned alongside the game
+    fn parse_game(&mut self, headers_only: bool) -> Result<(PgnGame, Option<Board>), PgnError> {
+        let mut game = PgnGame {
+            tags: Vec::new(),
+            comments: Vec::new(),
+            moves: Vec::new(),
+            result: String::from("*"),
+        };
+
+        let mut fen_position = (1, 1);
+
+        while let Some((token, line, column)) = self.peek()?.cloned() {
+            match token {
+                Token::Tag(name, value) => {
+                    if name == "FEN" {
+                        fen_position = (line, column);
+                    }
+                    game.tags.push((name, value));
+                }
+                Token::Comment(comment) => game.comments.push(comment),
+                _ => break,
+            }
+
+            self.next()?;
+        }
+
+        if let Some((_, result)) = game.tags.iter().find(|(name, _)| name == "Result") {
+            game.result = result.clone();
+        }
+
+        if headers_only {
+            return Ok((game, None));
+        }
+
+        let fen = match game.tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => fen.as_str(),
+            None => STARTING_FEN,
+        };
+
+        let board = Board::try_from_fen(fen).map_err(|e| {
+            PgnError::new(fen_position.0, fen_position.1, PgnErrorKind::InvalidFen(e))
+        })?;
+
+        let (moves, comments, result, board) = self.parse_line(board, None)?;
+
+        game.moves = moves;
+        game.comments.extend(comments);
+
+        if let Some(result) = result {
+            game.result = result;
+        }
+
+        Ok((game, Some(board)))
+    }
+
+    //Reads moves until the end of the mainline, or until the closing parenthesis when
+    //reading a variation opened at the given position
+    #[allow(clippy::type_complexity)]
+    fn parse_line(
+        &mut self,
+        mut board: Board,
+        variation_start: Option<(usize, usize)>,
+    ) -> Result<(Vec<PgnMove>, Vec<String>, Option<String>, Board), PgnError> {
+        let mut moves: Vec<PgnMove> = Vec::new();
+        let mut comments: Vec<String> = Vec::new();
+
+        loop {
+            let (token, line, column) = match self.next()? {
+                Some(token) => token,
+                None => match variation_start {
+                    Some((line, column)) => {
+                        return Err(PgnError::new(
+                            line,
+                            column,
+                            PgnErrorKind::UnterminatedVariation,
+                        ))
+                    }
+                    None => return Ok((moves, comments, None, board)),
+                },
+            };
+
+            let unexpected = |text: &str| {
+                PgnError::new(
+                    line,
+                    column,
+                    PgnErrorKind::UnexpectedToken(text.to_string()),
+                )
+            };
+
+            match token {
+                Token::San(san) => {
+                    let mv = board
+                        .parse_san(&san)
+                        .map_err(|e| PgnError::new(line, column, PgnErrorKind::InvalidMove(e)))?;
+
+                    //Games are replayed even after a rule ended them, as recorded games
+                    //sometimes carry on without anyone noticing
+                    board
+                        .replay_move(mv, false)
+                        .map_err(|e| PgnError::new(line, column, PgnErrorKind::IllegalMove(e)))?;
+
+                    moves.push(PgnMove {
+                        san,
+                        mv,
+                        nags: Vec::new(),
+                        comments: Vec::new(),
+                        variations: Vec::new(),
+                    });
+                }
+                Token::Comment(comment) => match moves.last_mut() {
+                    Some(last) => last.comments.push(comment),
+                    None => comments.push(comment),
+                },
+                Token::Nag(nag) => match moves.last_mut() {
+                    Some(last) => last.nags.push(nag),
+                    None => return Err(unexpected(&format!("${}", nag))),
+                },
+                Token::Open => {
+                    //A variation replaces the move before it
+                    let last = match moves.last_mut() {
+                        Some(last) => last,
+                        None => return Err(unexpected("(")),
+                    };
+
+                    let mut variation_board = board.clone();
+                    variation_board.unmake_move(false);
+
+                    let (variation, _, _, _) =
+                        self.parse_line(variation_board, Some((line, column)))?;
+                    last.variations.push(variation);
+                }
+                Token::Close if variation_start.is_some() => {
+                    return Ok((moves, comments, None, board));
+                }
+                Token::Close => return Err(unexpected(")")),
+                //The result ends the game, only the tags of another game may follow it
+                Token::Result(result) if variation_start.is_none() => {
+                    return match self.peek()? {
+                        None | Some((Token::Tag(_, _), _, _)) => {
+                            Ok((moves, comments, Some(result), board))
+                        }
+                        Some((token, line, column)) => Err(PgnError::new(
+                            *line,
+                            *column,
+                            PgnErrorKind::UnexpectedToken(token.to_text()),
+                        )),
+                    };
+                }
+                Token::Result(result) => return Err(unexpected(&result)),
+                Token::Tag(name, _) => return Err(unexpected(&format!("[{}", name))),
+            }
+        }
+    }
+}
+
+//Reads the first game of a PGN document, validating every move of its mainline and variations
+pub fn parse_pgn(pgn: &str) -> Result<PgnGame, PgnError> {
+    PgnParser::new(pgn, 1)
+        .parse_game(false)
+        .map(|(game, _)| game)
+}
+
+//Reads the games of a PGN database one at a time, so files of any size can be scanned.
+//A broken game is reported as an error and the reader carries on with the next one
+pub struct PgnReader<R: BufRead> {
+    reader: R,
+    headers_only: bool,
+    line: usize,
+    buffer: Vec<u8>,
+    //The first tag line of the next game, read while looking for the end of the last one
+    next_game_line: Option<String>,
+    done: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        PgnReader {
+            reader,
+            headers_only: false,
+            line: 0,
+            buffer: Vec::new(),
+            next_game_line: None,
+            done: false,
+        }
+    }
+
+    //Only reads the tag pairs, skipping the movetext without validating it
+    pub fn headers_only(reader: R) -> Self {
+        PgnReader {
+            headers_only: true,
+            ..PgnReader::new(reader)
+        }
+    }
+
+    fn read_line(&mut self) -> Result<Option<String>, PgnError> {
+        self.buffer.clear();
+
+        match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+                Ok(Some(String::from_utf8_lossy(&self.buffer).into_owned()))
+            }
+            Err(e) => Err(PgnError::new(
+                self.line + 1,
+                1,
+                PgnErrorKind::ReadFailed(e.to_string()),
+            )),
+        }
+    }
+
+    //Collects the text of the next game along with the line it starts on. A game ends
+    //where a tag pair follows its movetext, or at the first blank line after its result
+    fn read_game_text(&mut self) -> Result<Option<(String, usize)>, PgnError> {
+        let mut text = String::new();
+        let mut first_line = self.line + 1;
+        let mut in_movetext = false;
+        let mut in_comment = false;
+        let mut terminated = false;
+
+        if let Some(line) = self.next_game_line.take() {
+            text.push_str(&line);
+            first_line = self.line;
+        }
+
+        while let Some(line) = self.read_line()? {
+            let trimmed = line.trim_start();
+
+            if !in_comment && trimmed.starts_with('[') {
+                if in_movetext {
+                    self.next_game_line = Some(line);
+                    break;
+                }
+            } else if !in_comment && trimmed.is_empty() && text.is_empty() && !in_movetext {
+                first_line = self.line + 1;
+                continue;
+            } else if !in_comment && trimmed.is_empty() && terminated {
+                break;
+            } else if !in_comment && line.starts_with('%') {
+                //Escaped lines are left for the parser to skip
+            } else if !trimmed.is_empty() || in_comment {
+                let (symbols, ends_in_comment) = PgnReader::<R>::split_comments(&line, in_comment);
+
+                if let Some(last) = symbols.split_whitespace().last() {
+                    terminated = RESULT_TOKENS.contains(&last);
+                }
+
+                in_movetext = true;
+                in_comment = ends_in_comment;
+
+                if self.headers_only {
+                    continue;
+                }
+            }
+
+            text.push_str(&line);
+        }
+
+        //Without tags, a game read for its headers only has no text at all
+        if text.is_empty() && !in_movetext {
+            Ok(None)
+        } else {
+            Ok(Some((text, first_line)))
+        }
+    }
+
+    //The text of the line outside of comments, and whether a brace comment is still
+    //open at the end of the line
+    fn split_comments(line: &str, mut in_comment: bool) -> (String, bool) {
+        let mut symbols = String::new();
+
+        for ch in line.chars() {
+            match ch {
+                '}' if in_comment => {
+                    in_comment = false;
+                    symbols.push(' ');
+                }
+                '{' if !in_comment => in_comment = true,
+                ';' if !in_comment => break,
+                _ if !in_comment => symbols.push(ch),
+                _ => {}
+            }
+        }
+
+        (symbols, in_comment)
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.read_game_text() {
+            Ok(Some((text, first_line))) => Some(
+                PgnParser::new(&text, first_line)
+                    .parse_game(self.headers_only)
+                    .map(|(game, _)| game),
+            ),
+            Ok(None) => None,
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl GameOutcome {
+    pub fn get_pgn_result(&self) -> &'static str {
+        match self {
+            GameOutcome::Ongoing => "*",
+            GameOutcome::Checkmate {
+                winner: Team::White,
+            } => "1-0",
+            GameOutcome::Checkmate {
+                winner: Team::Black,
+            } => "0-1",
+            GameOutcome::Draw { .. } => "1/2-1/2",
+        }
+    }
+}
+
+impl Board {
+    //Loads the first game of a PGN document, leaving the board after its last mainline move
+    pub fn from_pgn(pgn: &str) -> Result<Board, PgnError> {
+        let (_, board) = PgnParser::new(pgn, 1).parse_game(false)?;
+        Ok(board.expect("The movetext is replayed unless only headers are read"))
+    }
+
+    //Renders the moves played so far as a PGN game. Tags that aren't given default
+    //to "?" except for the date and result, which come from the clock and the board
+    pub fn to_pgn(&self, tags: &[(String, String)]) -> String {
+        let mut replay = Board::from_fen(self.get_starting_fen());
+        let starting_fen = replay.to_fen();
+        let result = self.get_outcome().get_pgn_result();
+
+        let mut roster: Vec<(String, String)> = SEVEN_TAG_ROSTER
+            .iter()
+            .map(|name| {
+                let value = match *name {
+                    "Date" => pgn_date(SystemTime::now()),
+                    "Result" => String::from(result),
+                    _ => String::from("?"),
+                };
+
+                (String::from(*name), value)
+            })
+            .collect();
+
+        if starting_fen != STARTING_FEN {
+            roster.push((String::from("SetUp"), String::from("1")));
+            roster.push((String::from("FEN"), starting_fen));
+        }
+
+        for (name, value) in tags {
+            match roster.iter_mut().find(|(tag, _)| tag == name) {
+                Some(tag) => tag.1 = value.clone(),
+                None => roster.push((name.clone(), value.clone())),
+            }
+        }
+
+        let mut pgn = String::new();
+
+        for (name, value) in &roster {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+        }
+
+        pgn.push('\n');
+
+        //Replay the game from the start to write every move in the position it was played in
+        let mut tokens: Vec<String> = Vec::new();
+
+        for (i, mv) in self.get_move_history().into_iter().enumerate() {
+            let san = replay
+                .to_san(mv)
+                .expect("Moves in the history were legal when played");
+
+            match replay.get_current_team() {
+                Team::White => tokens.push(format!("{}.", replay.get_turn())),
+                Team::Black if i == 0 => tokens.push(format!("{}...", replay.get_turn())),
+                Team::Black => {}
+            }
+
+            tokens.push(san);
+
+            replay
+                .replay_move(mv, false)
+                .expect("Moves in the history were legal when played");
+        }
+
+        tokens.push(String::from(result));
+
+        let mut line_length = 0;
+
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+
+            line_length += token.len();
+            pgn.push_str(&token);
+        }
+
+        pgn.push('\n');
+        pgn
+    }
+}
+
+//The date in PGN's YYYY.MM.DD format, in UTC
+fn pgn_date(time: SystemTime) -> String {
+    let days = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    //Converts days since 1970-01-01 to a civil date, see
+    //http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
+
+impl fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnErrorKind::InvalidTag => write!(f, "tag pairs must look like [Name \"value\"]"),
+            PgnErrorKind::UnterminatedTag => write!(f, "tag pair is not closed"),
+            PgnErrorKind::UnterminatedComment => write!(f, "comment is not closed"),
+            PgnErrorKind::UnterminatedVariation => write!(f, "variation is not closed"),
+            PgnErrorKind::InvalidNag(nag) => {
+                write!(f, "'${}' is not a valid annotation glyph", nag)
+            }
+            PgnErrorKind::UnexpectedToken(token) => write!(f, "unexpected '{}'", token),
+            PgnErrorKind::InvalidFen(e) => write!(f, "{}", e),
+            PgnErrorKind::InvalidMove(e) => write!(f, "{}", e),
+            PgnErrorKind::IllegalMove(e) => write!(f, "{}", e),
+            PgnErrorKind::ReadFailed(e) => write!(f, "failed to read the PGN file ({})", e),
+        }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid PGN at line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for PgnError {}
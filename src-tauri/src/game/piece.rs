@@ -1,6 +1,8 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
+use super::square::Square;
+
 #[derive(Debug, Clone, Copy, Serialize_repr, PartialEq, Deserialize_repr)]
 #[repr(u8)]
 pub enum PieceType {
@@ -13,23 +15,32 @@ pub enum PieceType {
     King = 0b0110,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Team {
     White = 0b0000,
     Black = 0b1000,
 }
 
+impl Team {
+    pub fn opponent(&self) -> Team {
+        match self {
+            Team::White => Team::Black,
+            Team::Black => Team::White,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize)]
 pub struct Piece {
     piece_type: PieceType,
     team: Team,
-    index: usize,
+    index: Square,
     value: u8,
     moved: bool,
 }
 
 impl Piece {
-    pub fn new(piece_type: PieceType, team: Team, index: usize) -> Self {
+    pub fn new(piece_type: PieceType, team: Team, index: Square) -> Self {
         Piece {
             piece_type,
             team,
@@ -59,14 +70,31 @@ impl Piece {
         self.piece_type
     }
 
-    pub fn get_index(&self) -> usize {
+    pub fn get_index(&self) -> Square {
         self.index
     }
 
-    pub fn update_index(&mut self, index: usize) {
+    pub fn update_index(&mut self, index: Square) {
         self.index = index
     }
 
+    pub fn get_fen_char(&self) -> char {
+        let ch = match self.piece_type {
+            PieceType::Pawn => 'p',
+            PieceType::Bishop => 'b',
+            PieceType::Knight => 'n',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k',
+            PieceType::Empty => '.',
+        };
+
+        match self.team {
+            Team::White => ch.to_ascii_uppercase(),
+            Team::Black => ch,
+        }
+    }
+
     pub fn team_from_value(value: u8) -> Team {
         match value & (7) {
             0 => Team::White,
@@ -0,0 +1,299 @@
+use std::{error::Error, fmt};
+
+use super::{
+    board::Board,
+    moves::{IllegalMove, Move},
+    piece::PieceType,
+    square::Square,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SanError {
+    InvalidSyntax(String),
+    NoLegalMove(String),
+    MissingPromotionChoice(String),
+    AmbiguousMove { san: String, from: Vec<Square> },
+}
+
+//What a SAN string says about the move, before it is matched against the legal moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SanMove {
+    Castle {
+        kingside: bool,
+    },
+    Normal {
+        piece_type: PieceType,
+        from_file: Option<u8>,
+        from_rank: Option<u8>,
+        capture: bool,
+        to: Square,
+        promotion_piece: Option<PieceType>,
+    },
+}
+
+impl SanMove {
+    fn parse(san: &str) -> Option<SanMove> {
+        //Check, mate and annotation marks don't change which move is meant
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+
+        match san {
+            "O-O" | "0-0" => return Some(SanMove::Castle { kingside: true }),
+            "O-O-O" | "0-0-0" => return Some(SanMove::Castle { kingside: false }),
+            _ => {}
+        }
+
+        let mut chars: Vec<char> = san.chars().collect();
+
+        let piece_type = match chars.first().and_then(|ch| PieceType::from_san_char(*ch)) {
+            Some(piece_type) => {
+                chars.remove(0);
+                piece_type
+            }
+            None => PieceType::Pawn,
+        };
+
+        //Promotions are written as e8=Q, some sources leave out the '='
+        let promotion_piece = match chars.last().and_then(|ch| PieceType::from_san_char(*ch)) {
+            Some(promotion_piece) if piece_type == PieceType::Pawn => {
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(promotion_piece)
+            }
+            _ => None,
+        };
+
+        if chars.len() < 2 || matches!(promotion_piece, Some(PieceType::King)) {
+            return None;
+        }
+
+        let to: String = chars.split_off(chars.len() - 2).into_iter().collect();
+        let to = to.parse::<Square>().ok()?;
+
+        let capture = chars.last() == Some(&'x');
+        if capture {
+            chars.pop();
+        }
+
+        let (from_file, from_rank) = match chars[..] {
+            [] => (None, None),
+            [file @ 'a'..='h'] => (Some(file as u8 - b'a'), None),
+            [rank @ '1'..='8'] => (None, Some(rank as u8 - b'1')),
+            [file @ 'a'..='h', rank @ '1'..='8'] => {
+                (Some(file as u8 - b'a'), Some(rank as u8 - b'1'))
+            }
+            _ => return None,
+        };
+
+        //Pawn captures always name the file the pawn comes from
+        if piece_type == PieceType::Pawn && (capture != from_file.is_some() || from_rank.is_some())
+        {
+            return None;
+        }
+
+        Some(SanMove::Normal {
+            piece_type,
+            from_file,
+            from_rank,
+            capture,
+            to,
+            promotion_piece,
+        })
+    }
+}
+
+impl PieceType {
+    pub fn from_san_char(ch: char) -> Option<PieceType> {
+        match ch {
+            'B' => Some(PieceType::Bishop),
+            'N' => Some(PieceType::Knight),
+            'R' => Some(PieceType::Rook),
+            'Q' => Some(PieceType::Queen),
+            'K' => Some(PieceType::King),
+            _ => None,
+        }
+    }
+
+    //Pawns have no letter in SAN
+    pub fn get_san_char(&self) -> Option<char> {
+        match self {
+            PieceType::Bishop => Some('B'),
+            PieceType::Knight => Some('N'),
+            PieceType::Rook => Some('R'),
+            PieceType::Queen => Some('Q'),
+            PieceType::King => Some('K'),
+            PieceType::Pawn | PieceType::Empty => None,
+        }
+    }
+}
+
+impl Board {
+    //Renders a legal move of the current position in Standard Algebraic Notation
+    pub fn to_san(&self, mv: Move) -> Result<String, IllegalMove> {
+        let mv = self.find_legal_move(mv)?;
+        let piece_type = self.get_piece_type(mv.from);
+
+        let mut san = if let Some((rook_from, _)) = mv.castling_rook_squares() {
+            if rook_from > mv.from {
+                String::from("O-O")
+            } else {
+                String::from("O-O-O")
+            }
+        } else {
+            let is_capture = mv.en_passant || self.get_piece(mv.to).is_some();
+            let mut san = String::new();
+
+            match piece_type.get_san_char() {
+                Some(ch) => {
+                    san.push(ch);
+                    san.push_str(&self.get_san_disambiguation(mv, piece_type));
+                }
+                None if is_capture => san.push(mv.from.file_char()),
+                None => {}
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+
+            san.push_str(&mv.to.to_string());
+
+            if let Some(promotion_char) = mv.promotion_piece.and_then(|p| p.get_san_char()) {
+                san.push('=');
+                san.push(promotion_char);
+            }
+
+            san
+        };
+
+        //Play the move on a copy to find out if it gives check or mate
+        let mut cloned_board = self.clone();
+        cloned_board.make_move(mv, true)?;
+
+        if cloned_board.get_is_check() {
+            cloned_board.retain_legal_moves();
+            let is_mate = cloned_board.get_possible_moves().is_empty();
+
+            san.push(if is_mate { '#' } else { '+' });
+        }
+
+        Ok(san)
+    }
+
+    //Resolves a SAN string to the unique legal move of the current position it describes
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let san = san.trim();
+        let parsed = SanMove::parse(san).ok_or(SanError::InvalidSyntax(String::from(san)))?;
+
+        let candidates: Vec<Move> = self
+            .get_possible_moves()
+            .iter()
+            .filter(|mv| self.matches_san(**mv, parsed))
+            .copied()
+            .collect();
+
+        let with_promotion: Vec<Move> = candidates
+            .iter()
+            .filter(|mv| match parsed {
+                SanMove::Normal {
+                    promotion_piece, ..
+                } => mv.promotion_piece == promotion_piece,
+                SanMove::Castle { .. } => true,
+            })
+            .copied()
+            .collect();
+
+        match with_promotion[..] {
+            [mv] => Ok(mv),
+            [] if !candidates.is_empty() => {
+                Err(SanError::MissingPromotionChoice(String::from(san)))
+            }
+            [] => Err(SanError::NoLegalMove(String::from(san))),
+            _ => Err(SanError::AmbiguousMove {
+                san: String::from(san),
+                from: with_promotion.iter().map(|mv| mv.from).collect(),
+            }),
+        }
+    }
+
+    //Everything but the promotion piece, so a missing promotion can be told apart
+    //from a move that doesn't exist
+    fn matches_san(&self, mv: Move, parsed: SanMove) -> bool {
+        match parsed {
+            SanMove::Castle { kingside } => mv.castle && (mv.to > mv.from) == kingside,
+            SanMove::Normal {
+                piece_type,
+                from_file,
+                from_rank,
+                capture,
+                to,
+                ..
+            } => {
+                let is_capture = mv.en_passant || self.get_piece(mv.to).is_some();
+
+                !mv.castle
+                    && mv.to == to
+                    && self.get_piece_type(mv.from) == piece_type
+                    && from_file.is_none_or(|file| mv.from.file() == file)
+                    && from_rank.is_none_or(|rank| mv.from.rank() == rank)
+                    && (!capture || is_capture)
+            }
+        }
+    }
+
+    //The file, rank or whole square of the moving piece when another piece of the
+    //same type could also move to the target square
+    fn get_san_disambiguation(&self, mv: Move, piece_type: PieceType) -> String {
+        let rivals: Vec<&Move> = self
+            .get_possible_moves()
+            .iter()
+            .filter(|other| {
+                other.to == mv.to
+                    && other.from != mv.from
+                    && self.get_piece_type(other.from) == piece_type
+            })
+            .collect();
+
+        if rivals.is_empty() {
+            String::new()
+        } else if rivals
+            .iter()
+            .all(|other| other.from.file() != mv.from.file())
+        {
+            mv.from.file_char().to_string()
+        } else if rivals
+            .iter()
+            .all(|other| other.from.rank() != mv.from.rank())
+        {
+            mv.from.rank_char().to_string()
+        } else {
+            mv.from.to_string()
+        }
+    }
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::InvalidSyntax(san) => write!(f, "'{}' is not a valid SAN move", san),
+            SanError::NoLegalMove(san) => {
+                write!(f, "'{}' is not a legal move in this position", san)
+            }
+            SanError::MissingPromotionChoice(san) => {
+                write!(f, "'{}' must say which piece the pawn promotes to", san)
+            }
+            SanError::AmbiguousMove { san, from } => {
+                let from: Vec<String> = from.iter().map(|square| square.to_string()).collect();
+                write!(
+                    f,
+                    "'{}' is ambiguous, it could be played from {}",
+                    san,
+                    from.join(" or ")
+                )
+            }
+        }
+    }
+}
+
+impl Error for SanError {}
@@ -0,0 +1,142 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+//Squares are numbered like the board is drawn: 0 is a8, 7 is h8, 56 is a1 and 63 is h1.
+//Rows and cols follow that layout, while files and ranks follow algebraic notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "usize", into = "usize")]
+pub struct Square(u8);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SquareError {
+    OutOfRange(usize),
+    InvalidCoordinates(String),
+}
+
+impl Square {
+    pub const A1: Square = Square(56);
+    pub const E1: Square = Square(60);
+    pub const H1: Square = Square(63);
+    pub const A8: Square = Square(0);
+    pub const E8: Square = Square(4);
+    pub const H8: Square = Square(7);
+
+    pub fn new(index: usize) -> Option<Square> {
+        if index < 64 {
+            Some(Square(index as u8))
+        } else {
+            None
+        }
+    }
+
+    pub fn from_row_col(row: i32, col: i32) -> Option<Square> {
+        if !(0..8).contains(&row) || !(0..8).contains(&col) {
+            return None;
+        }
+
+        Some(Square((row * 8 + col) as u8))
+    }
+
+    //File 0 is the a-file and rank 0 is the first rank
+    pub fn from_file_rank(file: u8, rank: u8) -> Option<Square> {
+        Square::from_row_col(7 - rank as i32, file as i32)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn row(&self) -> i32 {
+        self.0 as i32 / 8
+    }
+
+    pub fn col(&self) -> i32 {
+        self.0 as i32 % 8
+    }
+
+    pub fn file(&self) -> u8 {
+        self.0 % 8
+    }
+
+    pub fn rank(&self) -> u8 {
+        7 - self.0 / 8
+    }
+
+    pub fn file_char(&self) -> char {
+        (b'a' + self.file()) as char
+    }
+
+    pub fn rank_char(&self) -> char {
+        (b'1' + self.rank()) as char
+    }
+
+    //Returns None when the offset falls off the board instead of wrapping around
+    pub fn offset(&self, row_offset: i32, col_offset: i32) -> Option<Square> {
+        Square::from_row_col(self.row() + row_offset, self.col() + col_offset)
+    }
+
+    pub fn is_light(&self) -> bool {
+        (self.row() + self.col()) % 2 == 0
+    }
+
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+}
+
+impl TryFrom<usize> for Square {
+    type Error = SquareError;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Square::new(index).ok_or(SquareError::OutOfRange(index))
+    }
+}
+
+impl From<Square> for usize {
+    fn from(square: Square) -> Self {
+        square.index()
+    }
+}
+
+impl FromStr for Square {
+    type Err = SquareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SquareError::InvalidCoordinates(String::from(s));
+        let mut chars = s.chars();
+
+        let file = match chars.next() {
+            Some(ch @ 'a'..='h') => ch as u8 - b'a',
+            _ => return Err(invalid()),
+        };
+
+        let rank = match chars.next() {
+            Some(ch @ '1'..='8') => ch as u8 - b'1',
+            _ => return Err(invalid()),
+        };
+
+        if chars.next().is_some() {
+            return Err(invalid());
+        }
+
+        Square::from_file_rank(file, rank).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file_char(), self.rank_char())
+    }
+}
+
+impl fmt::Display for SquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SquareError::OutOfRange(index) => write!(f, "square index {} is out of range", index),
+            SquareError::InvalidCoordinates(s) => write!(f, "'{}' is not a valid square", s),
+        }
+    }
+}
+
+impl Error for SquareError {}
@@ -0,0 +1,81 @@
+use std::{error::Error, fmt};
+
+use super::{board::Board, moves::Move, piece::PieceType, square::Square};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UciError {
+    InvalidSyntax(String),
+    NoLegalMove(String),
+}
+
+impl Move {
+    //Long algebraic notation as used by UCI engines, e.g. e2e4 or e7e8q.
+    //In Chess960 mode castling is written as the king capturing its own rook
+    pub fn to_uci(&self, chess960: bool) -> String {
+        let to = match self.castling_rook_squares() {
+            Some((rook_from, _)) if chess960 => rook_from,
+            _ => self.to,
+        };
+
+        let mut uci = format!("{}{}", self.from, to);
+
+        if let Some(ch) = self.promotion_piece.and_then(|p| p.get_san_char()) {
+            uci.push(ch.to_ascii_lowercase());
+        }
+
+        uci
+    }
+}
+
+impl Board {
+    //Resolves a UCI move to the legal move it describes, filling in the castling
+    //and en passant flags. Castling is accepted both as the king's two square step
+    //and as the king capturing its own rook
+    pub fn parse_uci(&self, uci: &str) -> Result<Move, UciError> {
+        let uci = uci.trim();
+        let invalid = || UciError::InvalidSyntax(String::from(uci));
+
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return Err(invalid());
+        }
+
+        let from: Square = uci[0..2].parse().map_err(|_| invalid())?;
+        let to: Square = uci[2..4].parse().map_err(|_| invalid())?;
+
+        let promotion_piece = match uci[4..].chars().next() {
+            Some(ch) => match PieceType::from_san_char(ch.to_ascii_uppercase()) {
+                Some(PieceType::King) | None => return Err(invalid()),
+                piece_type => piece_type,
+            },
+            None => None,
+        };
+
+        let is_king_taking_rook = |mv: &Move| match mv.castling_rook_squares() {
+            Some((rook_from, _)) => rook_from == to,
+            None => false,
+        };
+
+        self.get_possible_moves()
+            .iter()
+            .find(|mv| {
+                mv.from == from
+                    && (mv.to == to || is_king_taking_rook(mv))
+                    && mv.promotion_piece == promotion_piece
+            })
+            .copied()
+            .ok_or(UciError::NoLegalMove(String::from(uci)))
+    }
+}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UciError::InvalidSyntax(uci) => write!(f, "'{}' is not a valid UCI move", uci),
+            UciError::NoLegalMove(uci) => {
+                write!(f, "'{}' is not a legal move in this position", uci)
+            }
+        }
+    }
+}
+
+impl Error for UciError {}
@@ -0,0 +1,40 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{piece::Team, square::Square};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum PositionError {
+    MissingKing(Team),
+    TooManyKings(Team),
+    TooManyPawns(Team),
+    TooManyPieces(Team),
+    PawnOnBackRank(Square),
+    OpponentInCheck(Team),
+    InvalidEnPassant(Square),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::MissingKing(team) => write!(f, "{:?} has no king", team),
+            PositionError::TooManyKings(team) => write!(f, "{:?} has more than one king", team),
+            PositionError::TooManyPawns(team) => write!(f, "{:?} has more than 8 pawns", team),
+            PositionError::TooManyPieces(team) => {
+                write!(f, "{:?} has more than 16 pieces", team)
+            }
+            PositionError::PawnOnBackRank(square) => {
+                write!(f, "pawn on {} can't be on the first or last rank", square)
+            }
+            PositionError::OpponentInCheck(team) => {
+                write!(f, "{:?} is in check but it's not their turn", team)
+            }
+            PositionError::InvalidEnPassant(square) => write!(
+                f,
+                "en passant on {} has no pawn that just double stepped",
+                square
+            ),
+        }
+    }
+}
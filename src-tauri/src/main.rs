@@ -3,7 +3,15 @@
 
 use std::sync::Mutex;
 
-use rust_chess::game::{board::Board, moves::Move, piece::Piece};
+use rust_chess::game::{
+    attacks::AttackMap,
+    board::{Board, STARTING_FEN},
+    moves::{Move, MoveOutcome},
+    outcome::GameOutcome,
+    piece::{Piece, Team},
+    square::Square,
+};
+use serde::Serialize;
 
 #[tauri::command]
 fn get_board(board: tauri::State<'_, MutexBoard>) -> Board {
@@ -11,21 +19,144 @@ fn get_board(board: tauri::State<'_, MutexBoard>) -> Board {
 }
 
 #[tauri::command]
-fn get_position(index: usize) -> String {
-    Board::get_coordinates_from_index(index)
+fn get_position(square: Square) -> String {
+    square.to_string()
 }
 
 #[tauri::command]
-fn get_piece(index: usize, board: tauri::State<'_, MutexBoard>) -> Option<Piece> {
-    board.0.lock().unwrap().get_piece(index).to_owned()
+fn get_piece(square: Square, board: tauri::State<'_, MutexBoard>) -> Option<Piece> {
+    board.0.lock().unwrap().get_piece(square).to_owned()
 }
 
 #[tauri::command]
-fn make_move(mv: Move, board: tauri::State<'_, MutexBoard>) -> Board {
-    board.0.lock().unwrap().make_move(mv, false);
+fn make_move(mv: Move, board: tauri::State<'_, MutexBoard>) -> Result<MoveResponse, String> {
+    let mut board = board.0.lock().unwrap();
+
+    let played = board
+        .to_san(mv)
+        .and_then(|san| Ok((san, board.make_move(mv, false)?)));
+
+    match played {
+        Ok((san, outcome)) => Ok(MoveResponse {
+            board: board.to_owned(),
+            san,
+            outcome,
+        }),
+        Err(e) => {
+            tracing::warn!("Illegal move: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+fn make_san_move(san: String, board: tauri::State<'_, MutexBoard>) -> Result<MoveResponse, String> {
+    let mut board = board.0.lock().unwrap();
+
+    let played = board
+        .parse_san(&san)
+        .map_err(|e| e.to_string())
+        .and_then(|mv| {
+            let san = board.to_san(mv).map_err(|e| e.to_string())?;
+            let outcome = board.make_move(mv, false).map_err(|e| e.to_string())?;
+
+            Ok((san, outcome))
+        });
+
+    match played {
+        Ok((san, outcome)) => Ok(MoveResponse {
+            board: board.to_owned(),
+            san,
+            outcome,
+        }),
+        Err(e) => {
+            tracing::warn!("Illegal move: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[tauri::command]
+fn get_san(mv: Move, board: tauri::State<'_, MutexBoard>) -> Result<String, String> {
+    board
+        .0
+        .lock()
+        .unwrap()
+        .to_san(mv)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_outcome(board: tauri::State<'_, MutexBoard>) -> GameOutcome {
+    board.0.lock().unwrap().get_outcome()
+}
+
+#[tauri::command]
+fn get_fen(board: tauri::State<'_, MutexBoard>) -> String {
+    board.0.lock().unwrap().to_fen()
+}
+
+#[tauri::command]
+fn load_fen(fen: String, board: tauri::State<'_, MutexBoard>) -> Result<Board, String> {
+    let new_board = Board::try_from_fen(&fen).map_err(|e| e.to_string())?;
+
+    tracing::info!("Loaded FEN: {}", fen);
+
+    *board.0.lock().unwrap() = new_board.to_owned();
+    Ok(new_board)
+}
+
+#[tauri::command]
+fn load_pgn(pgn: String, board: tauri::State<'_, MutexBoard>) -> Result<Board, String> {
+    let new_board = Board::from_pgn(&pgn).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Loaded PGN game of {} moves",
+        new_board.get_move_history().len()
+    );
+
+    *board.0.lock().unwrap() = new_board.to_owned();
+    Ok(new_board)
+}
+
+#[tauri::command]
+fn export_pgn(board: tauri::State<'_, MutexBoard>) -> String {
+    board.0.lock().unwrap().to_pgn(&[])
+}
+
+#[tauri::command]
+fn undo_move(board: tauri::State<'_, MutexBoard>) -> Board {
+    board.0.lock().unwrap().unmake_move(false);
+    board.0.lock().unwrap().to_owned()
+}
+
+#[tauri::command]
+fn redo_move(board: tauri::State<'_, MutexBoard>) -> Board {
+    board.0.lock().unwrap().redo_move();
     board.0.lock().unwrap().to_owned()
 }
 
+#[tauri::command]
+fn claim_draw(board: tauri::State<'_, MutexBoard>) -> Result<GameOutcome, String> {
+    board
+        .0
+        .lock()
+        .unwrap()
+        .claim_draw()
+        .ok_or(String::from("There is no draw to claim"))
+}
+
+#[tauri::command]
+fn has_mating_material(team: Team, board: tauri::State<'_, MutexBoard>) -> bool {
+    board.0.lock().unwrap().has_mating_material(team)
+}
+
+#[tauri::command]
+fn get_threat_map(board: tauri::State<'_, MutexBoard>) -> AttackMap {
+    let board = board.0.lock().unwrap();
+    board.get_attack_map(board.get_current_team().opponent())
+}
+
 #[tauri::command]
 fn reset_board(board: tauri::State<'_, MutexBoard>) {
     board.0.lock().unwrap().reset();
@@ -33,10 +164,18 @@ fn reset_board(board: tauri::State<'_, MutexBoard>) {
 
 struct MutexBoard(Mutex<Board>);
 
+#[derive(Serialize)]
+struct MoveResponse {
+    board: Board,
+    //The move in Standard Algebraic Notation, so the frontend doesn't have to ask for it
+    san: String,
+    outcome: MoveOutcome,
+}
+
 fn main() {
     tracing_subscriber::fmt().pretty().init();
 
-    let fen_string = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+    let fen_string = STARTING_FEN;
 
     tracing::info!("Initializing with FEN: {}", fen_string);
 
@@ -49,7 +188,19 @@ fn main() {
             get_position,
             get_piece,
             make_move,
-            reset_board
+            make_san_move,
+            get_san,
+            reset_board,
+            get_outcome,
+            get_fen,
+            load_fen,
+            load_pgn,
+            export_pgn,
+            undo_move,
+            redo_move,
+            claim_draw,
+            has_mating_material,
+            get_threat_map
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,69 @@
+use rust_chess::game::{board::Board, piece::Team, square::Square};
+
+fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+#[test]
+fn pawns_attack_diagonally_but_not_forward() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+
+    assert!(board.is_attacked(sq("d3"), Team::White));
+    assert!(board.is_attacked(sq("f3"), Team::White));
+    assert!(!board.is_attacked(sq("e3"), Team::White));
+    assert!(!board.is_attacked(sq("e4"), Team::White));
+}
+
+#[test]
+fn attackers_of_a_square() {
+    let board = Board::from_fen("4k3/8/8/8/8/5N2/1B6/R3K3 w - - 0 1");
+
+    assert_eq!(
+        board.attackers_of(sq("d4"), Team::White),
+        vec![sq("f3"), sq("b2")]
+    );
+
+    assert_eq!(board.attackers_of(sq("a8"), Team::White), vec![sq("a1")]);
+    assert!(board.attackers_of(sq("d4"), Team::Black).is_empty());
+}
+
+#[test]
+fn sliding_attacks_stop_at_the_first_piece() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/P7/R3K3 w - - 0 1");
+
+    assert!(board.is_attacked(sq("a2"), Team::White));
+    assert!(!board.is_attacked(sq("a3"), Team::White));
+}
+
+#[test]
+fn attack_map_counts_every_attacker() {
+    let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    let white = board.get_attack_map(Team::White);
+
+    assert_eq!(white.get_team(), Team::White);
+    assert_eq!(white.get_count(sq("e3")), 2);
+    assert_eq!(white.get_count(sq("f3")), 3);
+    assert_eq!(white.get_count(sq("e4")), 0);
+    assert!(!white.is_attacked(sq("a1")));
+
+    let black = board.get_attack_map(Team::Black);
+    assert_eq!(black.get_count(sq("f6")), 3);
+    assert!(!black.is_attacked(sq("e3")));
+}
+
+#[test]
+fn check_and_castling_use_attacks() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2r w Q - 0 1");
+
+    assert!(board.is_check());
+
+    let board = Board::from_fen("3rk3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+    let castles: Vec<String> = board
+        .get_possible_moves()
+        .iter()
+        .filter(|mv| mv.castle)
+        .map(|mv| mv.to.to_string())
+        .collect();
+
+    assert_eq!(castles, vec!["g1"]);
+}
@@ -0,0 +1,66 @@
+use rust_chess::game::{bitboard::BitBoard, board::Board, moves::Move};
+
+const POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+];
+
+fn sorted(moves: &[Move]) -> Vec<String> {
+    let mut moves: Vec<String> = moves.iter().map(|mv| format!("{:?}", mv)).collect();
+    moves.sort();
+    moves
+}
+
+//Walks every line of the given depth, checking both generators agree in each position
+fn assert_same_moves(board: &Board, depth: u8) {
+    let bitboard = BitBoard::from_board(board);
+    let moves = board.get_possible_moves();
+
+    assert_eq!(
+        sorted(moves),
+        sorted(&bitboard.legal_moves()),
+        "legal moves of {}",
+        board.to_fen()
+    );
+
+    if depth == 0 {
+        return;
+    }
+
+    for mv in moves {
+        let mut next = board.clone();
+        next.make_move(*mv, false).unwrap();
+        assert_same_moves(&next, depth - 1);
+    }
+}
+
+#[test]
+fn generates_the_same_moves_as_board() {
+    for fen in POSITIONS {
+        assert_same_moves(&Board::from_fen(fen), 1);
+    }
+}
+
+#[test]
+fn perft_matches_published_counts() {
+    let expected: [&[u64]; 5] = [
+        &[20, 400, 8902, 197281],
+        &[48, 2039, 97862],
+        &[14, 191, 2812, 43238],
+        &[6, 264, 9467],
+        &[44, 1486, 62379],
+    ];
+
+    for (fen, counts) in POSITIONS.iter().zip(expected) {
+        let bitboard = BitBoard::from_board(&Board::from_fen(fen));
+
+        for (depth, nodes) in counts.iter().enumerate() {
+            let depth = depth as u8 + 1;
+            assert_eq!(bitboard.perft(depth), *nodes, "perft({}) of {}", depth, fen);
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use rust_chess::game::{
+    board::Board,
+    fen::{FenError, FenErrorKind, FenField},
+    moves::Move,
+    piece::{PieceType, Team},
+    square::Square,
+    validation::PositionError,
+};
+
+const POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+];
+
+fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+fn basic_move(from: &str, to: &str) -> Move {
+    Move::basic(sq(from), sq(to))
+}
+
+fn assert_same_position(a: &Board, b: &Board) {
+    assert_eq!(a.to_fen(), b.to_fen());
+    assert_eq!(a.get_current_team(), b.get_current_team());
+
+    let moves = |board: &Board| -> Vec<String> {
+        let mut moves: Vec<String> = board
+            .get_possible_moves()
+            .iter()
+            .map(|mv| format!("{:?}", mv))
+            .collect();
+        moves.sort();
+        moves
+    };
+
+    assert_eq!(moves(a), moves(b));
+}
+
+#[test]
+fn fen_round_trips() {
+    for fen in POSITIONS {
+        let board = Board::from_fen(fen);
+
+        assert_eq!(board.to_fen(), fen);
+        assert_same_position(&board, &Board::from_fen(&board.to_fen()));
+    }
+}
+
+#[test]
+fn placement_only_fen_defaults_to_starting_state() {
+    let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+
+    assert_eq!(board.to_fen(), POSITIONS[0]);
+}
+
+#[test]
+fn to_fen_tracks_played_moves() {
+    let mut board = Board::from_fen(POSITIONS[0]);
+
+    board.make_move(basic_move("e2", "e4"), false).unwrap();
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+
+    board.make_move(basic_move("g8", "f6"), false).unwrap();
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2"
+    );
+
+    assert_same_position(&board, &Board::from_fen(&board.to_fen()));
+}
+
+#[test]
+fn round_trip_keeps_castling_rights_and_en_passant() {
+    let mut board = Board::from_fen("r3k2r/pppppppp/8/8/1P6/8/P1PPPPPP/R3K2R b KQkq b3 0 1");
+
+    //Moving a rook away and back loses that side's castling right
+    board.make_move(basic_move("h8", "g8"), false).unwrap();
+    board.make_move(basic_move("h1", "g1"), false).unwrap();
+    board.make_move(basic_move("g8", "h8"), false).unwrap();
+    board.make_move(basic_move("g1", "h1"), false).unwrap();
+
+    assert_eq!(
+        board.to_fen(),
+        "r3k2r/pppppppp/8/8/1P6/8/P1PPPPPP/R3K2R b Qq - 4 3"
+    );
+
+    let reloaded = Board::from_fen(&board.to_fen());
+    assert_same_position(&board, &reloaded);
+    assert!(!reloaded
+        .get_possible_moves()
+        .iter()
+        .any(|mv| mv.castle && mv.to == sq("g8")));
+
+    //En passant is only available right after the double step
+    let mut board = Board::from_fen("4k3/8/8/8/1p6/8/P7/4K3 w - - 0 1");
+    board.make_move(basic_move("a2", "a4"), false).unwrap();
+
+    let reloaded = Board::from_fen(&board.to_fen());
+    assert_same_position(&board, &reloaded);
+    assert!(reloaded
+        .get_possible_moves()
+        .iter()
+        .any(|mv| mv.en_passant && mv.to == sq("a3")));
+    assert_eq!(reloaded.get_current_team(), Team::Black);
+    assert_eq!(reloaded.get_piece_type(sq("a4")), PieceType::Pawn);
+}
+
+#[test]
+fn halfmove_clock_saturates() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 65535 1");
+    assert_eq!(board.perft(2), 68);
+
+    //The game is already drawn by the seventy-five move rule, so only a simulated move plays
+    board.make_move(basic_move("a1", "a2"), true).unwrap();
+    assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/R7/4K3 b - - 65535 1");
+}
+
+#[test]
+fn fullmove_number_is_bounded() {
+    assert!(Board::try_from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 0 65535").is_err());
+
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 0 10000");
+    board.make_move(basic_move("e8", "d7"), false).unwrap();
+    assert_eq!(board.to_fen(), "8/3k4/8/8/8/8/8/R3K3 w - - 1 10001");
+}
+
+fn fen_error(fen: &str) -> FenError {
+    match Board::try_from_fen(fen) {
+        Ok(_) => panic!("'{}' should not load", fen),
+        Err(e) => e,
+    }
+}
+
+fn assert_fen_error(fen: &str, field: FenField, column: usize, kind: FenErrorKind) {
+    assert_eq!(
+        fen_error(fen),
+        FenError::new(field, column, kind),
+        "{}",
+        fen
+    );
+}
+
+#[test]
+fn placement_errors_point_at_the_problem() {
+    assert_fen_error(
+        "rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        FenField::Placement,
+        14,
+        FenErrorKind::InvalidPiece('x'),
+    );
+    assert_fen_error(
+        "rnbqkbnr/pppppppp/7/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        FenField::Placement,
+        20,
+        FenErrorKind::InvalidRankLength { rank: 6, length: 7 },
+    );
+    assert_fen_error(
+        "rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        FenField::Placement,
+        18,
+        FenErrorKind::InvalidRankLength { rank: 7, length: 9 },
+    );
+    assert_fen_error(
+        "8/8/8/8/8/8/8 w - - 0 1",
+        FenField::Placement,
+        1,
+        FenErrorKind::InvalidRankCount(7),
+    );
+    assert_fen_error(
+        "rnbqkbnr/pppppppp/44/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        FenField::Placement,
+        20,
+        FenErrorKind::ConsecutiveDigits(String::from("44")),
+    );
+    assert_fen_error(
+        "3k13/8/8/8/8/8/8/4K3 w - - 0 1",
+        FenField::Placement,
+        4,
+        FenErrorKind::ConsecutiveDigits(String::from("13")),
+    );
+}
+
+#[test]
+fn columns_count_characters() {
+    //The ideographic space separating the fields takes three bytes
+    assert_fen_error(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR\u{3000}x KQkq - 0 1",
+        FenField::SideToMove,
+        45,
+        FenErrorKind::InvalidSideToMove(String::from("x")),
+    );
+    assert_fen_error(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR\u{3000}w\u{3000}Kéq - 0 1",
+        FenField::Castling,
+        48,
+        FenErrorKind::InvalidCastling('é'),
+    );
+    assert_fen_error(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ñ",
+        FenField::FullmoveNumber,
+        58,
+        FenErrorKind::TooManyFields(7),
+    );
+}
+
+#[test]
+fn field_errors_point_at_the_problem() {
+    const PLACEMENT: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+    let fen = |fields: &str| format!("{} {}", PLACEMENT, fields);
+
+    assert_fen_error(
+        &fen("x KQkq - 0 1"),
+        FenField::SideToMove,
+        45,
+        FenErrorKind::InvalidSideToMove(String::from("x")),
+    );
+    assert_fen_error(
+        &fen("w KQxq - 0 1"),
+        FenField::Castling,
+        49,
+        FenErrorKind::InvalidCastling('x'),
+    );
+    assert_fen_error(
+        &fen("w KQKq - 0 1"),
+        FenField::Castling,
+        49,
+        FenErrorKind::InvalidCastling('K'),
+    );
+    assert_fen_error(
+        "4k3/8/8/8/8/8/8/4K3 w KQ - 0 1",
+        FenField::Castling,
+        23,
+        FenErrorKind::InvalidCastling('K'),
+    );
+    assert_fen_error(
+        "r3k3/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+        FenField::Castling,
+        28,
+        FenErrorKind::InvalidCastling('k'),
+    );
+    assert_fen_error(
+        "r3k2r/8/8/8/8/8/8/R4K1R w Qq - 0 1",
+        FenField::Castling,
+        27,
+        FenErrorKind::InvalidCastling('Q'),
+    );
+    assert_fen_error(
+        &fen("w KQkq e4 0 1"),
+        FenField::EnPassant,
+        52,
+        FenErrorKind::InvalidEnPassant(String::from("e4")),
+    );
+    assert_fen_error(
+        &fen("w KQkq - x 1"),
+        FenField::HalfmoveClock,
+        54,
+        FenErrorKind::InvalidNumber(String::from("x")),
+    );
+    assert_fen_error(
+        &fen("w KQkq - 0 y"),
+        FenField::FullmoveNumber,
+        56,
+        FenErrorKind::InvalidNumber(String::from("y")),
+    );
+    assert_fen_error(
+        &fen("w KQkq - 0 1 extra"),
+        FenField::FullmoveNumber,
+        58,
+        FenErrorKind::TooManyFields(7),
+    );
+}
+
+fn assert_position_error(fen: &str, problems: Vec<PositionError>) {
+    assert_fen_error(
+        fen,
+        FenField::Placement,
+        1,
+        FenErrorKind::InvalidPosition(problems),
+    );
+}
+
+#[test]
+fn impossible_positions_are_rejected() {
+    assert_position_error(
+        "4k3/8/8/8/8/8/8/8 w - - 0 1",
+        vec![PositionError::MissingKing(Team::White)],
+    );
+    assert_position_error(
+        "3kk3/8/8/8/8/8/8/4K3 w - - 0 1",
+        vec![PositionError::TooManyKings(Team::Black)],
+    );
+    assert_position_error(
+        "4k3/8/8/8/8/P7/PPPPPPPP/4K3 w - - 0 1",
+        vec![PositionError::TooManyPawns(Team::White)],
+    );
+    assert_position_error(
+        "rnbqkbnr/pppppppp/n7/8/8/8/8/4K3 w - - 0 1",
+        vec![PositionError::TooManyPieces(Team::Black)],
+    );
+    assert_position_error(
+        "P3k3/8/8/8/8/8/8/p3K3 w - - 0 1",
+        vec![
+            PositionError::PawnOnBackRank(sq("a8")),
+            PositionError::PawnOnBackRank(sq("a1")),
+        ],
+    );
+    assert_position_error(
+        "4k3/8/8/8/8/8/8/4R1K1 w - - 0 1",
+        vec![PositionError::OpponentInCheck(Team::Black)],
+    );
+    assert_position_error(
+        "4k3/8/8/8/8/8/8/4K3 b - e3 0 1",
+        vec![PositionError::InvalidEnPassant(sq("e3"))],
+    );
+
+    //Every problem is reported, not only the first one
+    assert_position_error(
+        "8/8/8/8/8/8/8/8 w - - 0 1",
+        vec![
+            PositionError::MissingKing(Team::White),
+            PositionError::MissingKing(Team::Black),
+        ],
+    );
+}
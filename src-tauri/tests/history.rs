@@ -0,0 +1,129 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    outcome::{DrawReason, GameOutcome},
+};
+
+fn play(board: &mut Board, moves: &str) {
+    for uci in moves.split_whitespace() {
+        let mv = board.parse_uci(uci).unwrap();
+        board.make_move(mv, false).unwrap();
+    }
+}
+
+#[test]
+fn make_unmake_and_redo() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "e2e4");
+    let after_e4 = board.to_fen();
+
+    play(&mut board, "e7e5");
+    let after_e5 = board.to_fen();
+
+    let undone = board.unmake_move(false).unwrap();
+    assert_eq!(undone.to_uci(false), "e7e5");
+    assert_eq!(board.to_fen(), after_e4);
+    assert_eq!(board.get_possible_moves().len(), 20);
+    assert!(board.can_redo());
+
+    let redone = board.redo_move().unwrap();
+    assert_eq!(redone.mv.to_uci(false), "e7e5");
+    assert_eq!(board.to_fen(), after_e5);
+    assert_eq!(board.get_move_history().len(), 2);
+    assert!(!board.can_redo());
+
+    board.unmake_move(false);
+    board.unmake_move(false);
+    assert_eq!(board.to_fen(), STARTING_FEN);
+    assert!(board.unmake_move(false).is_none());
+
+    //Moves are redone in the order they were played
+    board.redo_move();
+    board.redo_move();
+    assert_eq!(board.to_fen(), after_e5);
+    assert!(board.redo_move().is_none());
+}
+
+#[test]
+fn a_new_move_clears_the_redo_stack() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "e2e4 e7e5");
+
+    board.unmake_move(false);
+    play(&mut board, "c7c5");
+
+    assert!(!board.can_redo());
+    assert!(board.redo_move().is_none());
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"
+    );
+}
+
+#[test]
+fn a_failed_redo_keeps_the_move() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8 g1f3");
+
+    //Back to the starting position for the third time, where the draw is claimed
+    board.unmake_move(false);
+    assert_eq!(
+        board.get_claimable_draw(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+    board.claim_draw();
+
+    assert!(board.redo_move().is_none());
+    assert!(board.can_redo());
+}
+
+#[test]
+fn unmake_restores_the_fen() {
+    let cases = [
+        //Capture
+        (
+            "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+            "e4d5",
+        ),
+        //Castling on both sides
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 3 20", "e1g1"),
+        ("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 3 20", "e8c8"),
+        //Promotion, with and without a capture
+        ("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 40", "a7a8q"),
+        ("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 40", "a7b8n"),
+        //En passant
+        ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 30", "e5d6"),
+        ("4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 30", "e4d3"),
+        //A rook move that loses a castling right
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "h1h8"),
+    ];
+
+    for (fen, uci) in cases {
+        let mut board = Board::from_fen(fen);
+        let moves = board.get_possible_moves().len();
+        let hash = board.get_hash();
+
+        play(&mut board, uci);
+        assert_ne!(board.to_fen(), fen, "{} was played", uci);
+
+        board.unmake_move(false);
+        assert_eq!(board.to_fen(), fen, "after taking back {}", uci);
+        assert_eq!(
+            board.get_possible_moves().len(),
+            moves,
+            "after taking back {}",
+            uci
+        );
+        assert_eq!(board.get_hash(), hash, "after taking back {}", uci);
+    }
+}
+
+#[test]
+fn unmake_restores_the_outcome() {
+    let mut board = Board::from_fen("7k/8/6K1/8/8/8/8/R7 w - - 0 1");
+    play(&mut board, "a1a8");
+    assert!(board.get_outcome().is_over());
+
+    board.unmake_move(false);
+    assert_eq!(board.get_outcome(), GameOutcome::Ongoing);
+    assert!(!board.is_check());
+}
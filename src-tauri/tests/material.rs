@@ -0,0 +1,86 @@
+use rust_chess::game::{
+    board::Board,
+    outcome::{DrawReason, GameOutcome},
+    piece::Team,
+};
+
+//(FEN, white has mating material, black has mating material)
+const MATERIAL: [(&str, bool, bool); 14] = [
+    //Kings only
+    ("4k3/8/8/8/8/8/8/4K3 w - - 0 1", false, false),
+    //A single minor piece
+    ("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", false, false),
+    ("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1", false, false),
+    //Bishops on squares of the same color, on either side
+    ("4k3/8/8/8/8/8/1B6/2B1K3 w - - 0 1", false, false),
+    ("5b2/4k3/8/8/8/8/8/2B1K3 w - - 0 1", false, false),
+    //Bishops on squares of both colors
+    ("4k3/8/8/8/8/8/8/2BBK3 w - - 0 1", true, false),
+    ("2b5/4k3/8/8/8/8/8/2B1K3 w - - 0 1", true, true),
+    //Two knights can mate if the other side blunders
+    ("4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1", true, false),
+    //A minor piece can mate a king boxed in by its own knight or bishop
+    ("4k3/8/8/8/8/8/8/1n2K1B1 w - - 0 1", true, true),
+    //but not one next to a queen, which would be giving check itself
+    ("4k3/8/8/8/8/8/8/1q2K1N1 w - - 0 1", false, true),
+    ("4k3/8/8/8/8/8/8/1r2K1N1 w - - 0 1", true, true),
+    //Pawns, rooks and queens can always mate
+    ("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", true, false),
+    ("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", true, false),
+    ("3qk3/8/8/8/8/8/8/4K3 w - - 0 1", false, true),
+];
+
+#[test]
+fn mating_material() {
+    for (fen, white, black) in MATERIAL {
+        let board = Board::from_fen(fen);
+
+        assert_eq!(
+            board.has_mating_material(Team::White),
+            white,
+            "white in {}",
+            fen
+        );
+        assert_eq!(
+            board.has_mating_material(Team::Black),
+            black,
+            "black in {}",
+            fen
+        );
+    }
+}
+
+#[test]
+fn dead_positions_are_drawn() {
+    for (fen, white, black) in MATERIAL {
+        let board = Board::from_fen(fen);
+        let dead = !white && !black;
+
+        assert_eq!(board.is_insufficient_material(), dead, "{}", fen);
+
+        let expected = if dead {
+            GameOutcome::Draw {
+                reason: DrawReason::InsufficientMaterial,
+            }
+        } else {
+            GameOutcome::Ongoing
+        };
+        assert_eq!(board.get_outcome(), expected, "{}", fen);
+    }
+}
+
+#[test]
+fn capturing_the_last_mating_piece_ends_the_game() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1");
+    assert_eq!(board.get_outcome(), GameOutcome::Ongoing);
+
+    let mv = board.parse_uci("e1e2").unwrap();
+    let outcome = board.make_move(mv, false).unwrap();
+
+    assert_eq!(
+        outcome.status,
+        GameOutcome::Draw {
+            reason: DrawReason::InsufficientMaterial
+        }
+    );
+}
@@ -0,0 +1,144 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    moves::{IllegalMove, Move},
+    outcome::GameOutcome,
+    piece::{PieceType, Team},
+    square::Square,
+};
+
+fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+fn basic_move(from: &str, to: &str) -> Move {
+    Move::basic(sq(from), sq(to))
+}
+
+fn illegal(fen: &str, mv: Move) -> IllegalMove {
+    Board::from_fen(fen).make_move(mv, false).unwrap_err()
+}
+
+#[test]
+fn moves_of_the_wrong_piece() {
+    assert_eq!(
+        illegal(STARTING_FEN, basic_move("e3", "e4")),
+        IllegalMove::NoPieceOnSquare(sq("e3"))
+    );
+    assert_eq!(
+        illegal(STARTING_FEN, basic_move("e7", "e5")),
+        IllegalMove::WrongSideToMove(Team::Black)
+    );
+    assert_eq!(
+        illegal(STARTING_FEN, basic_move("g1", "g3")),
+        IllegalMove::CannotReachSquare {
+            from: sq("g1"),
+            to: sq("g3"),
+        }
+    );
+}
+
+#[test]
+fn moves_that_leave_the_king_in_check() {
+    //Into check
+    assert_eq!(
+        illegal("3rk3/8/8/8/8/8/8/4K3 w - - 0 1", basic_move("e1", "d2")),
+        IllegalMove::LeavesKingInCheck
+    );
+
+    //Staying in check
+    assert_eq!(
+        illegal("4k3/4r3/8/8/8/8/8/R3K3 w - - 0 1", basic_move("a1", "a2")),
+        IllegalMove::LeavesKingInCheck
+    );
+
+    //Moving a pinned piece off the pin
+    assert_eq!(
+        illegal("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1", basic_move("e2", "d3")),
+        IllegalMove::LeavesKingInCheck
+    );
+}
+
+#[test]
+fn promotions_need_a_piece() {
+    assert_eq!(
+        illegal("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", basic_move("a7", "a8")),
+        IllegalMove::MissingPromotionChoice
+    );
+}
+
+#[test]
+fn no_moves_after_the_game_is_over() {
+    let mut board = Board::from_fen(STARTING_FEN);
+
+    for (from, to) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+        board.make_move(basic_move(from, to), false).unwrap();
+    }
+
+    let mate = GameOutcome::Checkmate {
+        winner: Team::Black,
+    };
+
+    assert_eq!(
+        board.make_move(basic_move("e2", "e4"), false).unwrap_err(),
+        IllegalMove::GameOver(mate)
+    );
+    assert_eq!(board.get_outcome(), mate);
+}
+
+#[test]
+fn outcome_of_a_capture() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2");
+    let outcome = board.make_move(basic_move("e4", "d5"), false).unwrap();
+
+    assert_eq!(outcome.mv.to_uci(false), "e4d5");
+    assert_eq!(outcome.captured, Some(PieceType::Pawn));
+    assert!(!outcome.castle);
+    assert_eq!(outcome.promotion, None);
+    assert!(!outcome.check);
+    assert_eq!(outcome.status, GameOutcome::Ongoing);
+}
+
+#[test]
+fn outcome_fills_in_special_moves() {
+    //En passant takes the pawn beside the capturing one
+    let mut board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2");
+    let outcome = board.make_move(basic_move("e5", "d6"), false).unwrap();
+
+    assert!(outcome.mv.en_passant);
+    assert_eq!(outcome.captured, Some(PieceType::Pawn));
+    assert_eq!(board.get_piece_type(sq("d5")), PieceType::Empty);
+
+    //A king step of two squares is a castle
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+    let outcome = board.make_move(basic_move("e1", "g1"), false).unwrap();
+
+    assert!(outcome.castle);
+    assert!(outcome.mv.castle);
+    assert_eq!(outcome.captured, None);
+    assert_eq!(board.get_piece_type(sq("f1")), PieceType::Rook);
+
+    //Promoting with check
+    let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+    let outcome = board
+        .make_move(Move::promotion(sq("a7"), sq("a8"), PieceType::Queen), false)
+        .unwrap();
+
+    assert_eq!(outcome.promotion, Some(PieceType::Queen));
+    assert!(outcome.check);
+    assert_eq!(outcome.status, GameOutcome::Ongoing);
+}
+
+#[test]
+fn outcome_of_a_mating_move() {
+    let mut board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let outcome = board.make_move(basic_move("a1", "a8"), false).unwrap();
+
+    assert!(outcome.check);
+    assert_eq!(
+        outcome.status,
+        GameOutcome::Checkmate {
+            winner: Team::White
+        }
+    );
+}
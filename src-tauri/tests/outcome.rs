@@ -0,0 +1,88 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    outcome::{DrawReason, GameOutcome},
+    piece::Team,
+};
+
+const STALEMATE: GameOutcome = GameOutcome::Draw {
+    reason: DrawReason::Stalemate,
+};
+
+fn play(board: &mut Board, moves: &str) -> GameOutcome {
+    let mut status = board.get_outcome();
+
+    for uci in moves.split_whitespace() {
+        let mv = board.parse_uci(uci).unwrap();
+        status = board.make_move(mv, false).unwrap().status;
+    }
+
+    status
+}
+
+#[test]
+fn checkmate_by_either_side() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    let status = play(&mut board, "e2e4 e7e5 d1h5 b8c6 f1c4 g8f6 h5f7");
+
+    assert_eq!(
+        status,
+        GameOutcome::Checkmate {
+            winner: Team::White
+        }
+    );
+    assert_eq!(board.get_outcome().get_winner(), Some(Team::White));
+    assert!(board.is_check());
+    assert!(board.get_possible_moves().is_empty());
+
+    let mut board = Board::from_fen(STARTING_FEN);
+    let status = play(&mut board, "f2f3 e7e5 g2g4 d8h4");
+
+    assert_eq!(
+        status,
+        GameOutcome::Checkmate {
+            winner: Team::Black
+        }
+    );
+    assert_eq!(board.get_outcome().get_winner(), Some(Team::Black));
+}
+
+#[test]
+fn checkmate_from_fen() {
+    let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+    assert_eq!(
+        board.get_outcome(),
+        GameOutcome::Checkmate {
+            winner: Team::White
+        }
+    );
+
+    let board = Board::from_fen("6k1/8/8/8/8/8/5PPP/r5K1 w - - 0 1");
+    assert_eq!(
+        board.get_outcome(),
+        GameOutcome::Checkmate {
+            winner: Team::Black
+        }
+    );
+}
+
+#[test]
+fn stalemate_of_either_side() {
+    let mut board = Board::from_fen("7k/8/6K1/8/8/8/8/5Q2 w - - 0 1");
+    assert_eq!(play(&mut board, "f1f7"), STALEMATE);
+    assert!(!board.is_check());
+    assert!(board.get_outcome().get_winner().is_none());
+
+    let board = Board::from_fen("8/8/8/8/8/6k1/5q2/7K w - - 0 1");
+    assert_eq!(board.get_outcome(), STALEMATE);
+    assert!(!board.is_check());
+}
+
+#[test]
+fn check_with_an_escape_is_not_mate() {
+    let mut board = Board::from_fen("6k1/5pp1/8/8/8/8/8/R5K1 w - - 0 1");
+    let status = play(&mut board, "a1a8");
+
+    assert_eq!(status, GameOutcome::Ongoing);
+    assert!(board.is_check());
+    assert_eq!(board.get_possible_moves().len(), 1);
+}
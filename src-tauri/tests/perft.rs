@@ -0,0 +1,85 @@
+use rust_chess::game::board::Board;
+
+//Node counts from the Chess Programming Wiki perft results
+const STARTING_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen);
+
+    for (depth, nodes) in expected.iter().enumerate() {
+        let depth = depth as u8 + 1;
+        assert_eq!(board.perft(depth), *nodes, "perft({}) of {}", depth, fen);
+    }
+}
+
+#[test]
+fn starting_position() {
+    assert_perft(STARTING_POSITION, &[20, 400, 8902]);
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5, &[44, 1486]);
+}
+
+#[test]
+fn en_passant_edge_cases() {
+    //Capturing en passant out of check
+    assert_perft("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1", &[15, 126, 1928]);
+    //En passant that would expose the king along the rank
+    assert_perft("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1", &[18, 92, 1670]);
+}
+
+#[test]
+fn castling_edge_cases() {
+    //Castling that gives check
+    assert_perft("5k2/8/8/8/8/8/8/4K2R w K - 0 1", &[15, 66, 1198]);
+    assert_perft("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", &[16, 71, 1286]);
+    //Castling through or out of attacked squares
+    assert_perft("r3k2r/1b4bq/8/8/8/8/7B/R3K2R w KQkq - 0 1", &[26, 1141]);
+}
+
+#[test]
+fn promotion_edge_cases() {
+    //Promoting out of check
+    assert_perft("2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1", &[11, 133, 1442]);
+    //Underpromoting to give check
+    assert_perft("8/P1k5/K7/8/8/8/8/8 w - - 0 1", &[6, 27, 273]);
+    //Promotions leading to stalemate and checkmate
+    assert_perft("8/k1P5/8/1K6/8/8/8/8 w - - 0 1", &[10, 25, 268]);
+}
+
+#[test]
+fn self_stalemate() {
+    assert_perft("8/8/2k5/5q2/5n2/8/5K2/8 b - - 0 1", &[37, 183, 6559]);
+}
+
+//Deeper searches take a while in debug builds, run them with `cargo test --release -- --ignored`
+#[test]
+#[ignore]
+fn deep_perft() {
+    assert_perft(STARTING_POSITION, &[20, 400, 8902, 197281]);
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624]);
+    assert_perft(POSITION_4, &[6, 264, 9467, 422333]);
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
@@ -0,0 +1,285 @@
+use std::io::Cursor;
+
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    outcome::{DrawReason, GameOutcome},
+    pgn::{parse_pgn, PgnError, PgnErrorKind, PgnMove, PgnReader},
+    san::SanError,
+};
+
+fn sans(moves: &[PgnMove]) -> Vec<&str> {
+    moves.iter().map(|mv| mv.san.as_str()).collect()
+}
+
+fn play(board: &mut Board, moves: &str) {
+    for san in moves.split_whitespace() {
+        let mv = board.parse_san(san).unwrap();
+        board.make_move(mv, false).unwrap();
+    }
+}
+
+#[test]
+fn tags() {
+    let game = parse_pgn(
+        "[Event \"Club \\\"Open\\\"\"]\n[Site \"Here\"]\n[Result \"1-0\"]\n\n1. e4 e5 1-0\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        game.tags,
+        vec![
+            (String::from("Event"), String::from("Club \"Open\"")),
+            (String::from("Site"), String::from("Here")),
+            (String::from("Result"), String::from("1-0")),
+        ]
+    );
+    assert_eq!(game.result, "1-0");
+    assert_eq!(sans(&game.moves), vec!["e4", "e5"]);
+}
+
+#[test]
+fn brace_and_line_comments() {
+    let game = parse_pgn("{Before} 1. e4 {A brace\ncomment} e5 ; to the end of the line\n2. Nf3 *")
+        .unwrap();
+
+    assert_eq!(game.comments, vec!["Before"]);
+    assert_eq!(game.moves[0].comments, vec!["A brace\ncomment"]);
+    assert_eq!(game.moves[1].comments, vec!["to the end of the line"]);
+    assert!(game.moves[2].comments.is_empty());
+}
+
+#[test]
+fn nested_variations() {
+    let game = parse_pgn("1. e4 e5 (1... c5 2. Nf3 (2. Nc3 Nc6) d6) 2. Nf3 *").unwrap();
+
+    assert_eq!(sans(&game.moves), vec!["e4", "e5", "Nf3"]);
+
+    let variation = &game.moves[1].variations[0];
+    assert_eq!(sans(variation), vec!["c5", "Nf3", "d6"]);
+    assert_eq!(sans(&variation[1].variations[0]), vec!["Nc3", "Nc6"]);
+}
+
+#[test]
+fn suffix_annotations_and_nags() {
+    let game = parse_pgn("1. e4!? e5?? 2. Nf3! $14 Nc6 $2 *").unwrap();
+
+    assert_eq!(sans(&game.moves), vec!["e4", "e5", "Nf3", "Nc6"]);
+    assert_eq!(game.moves[0].nags, vec![5]);
+    assert_eq!(game.moves[1].nags, vec![4]);
+    assert_eq!(game.moves[2].nags, vec![1, 14]);
+    assert_eq!(game.moves[3].nags, vec![2]);
+}
+
+#[test]
+fn escaped_lines_are_skipped() {
+    let game = parse_pgn("% Not PGN: [ { (\n[Event \"Escaped\"]\n\n1. e4 *").unwrap();
+
+    assert_eq!(game.tags[0].1, "Escaped");
+    assert_eq!(sans(&game.moves), vec!["e4"]);
+}
+
+#[test]
+fn move_numbers_are_split_from_moves() {
+    let black_to_move = parse_pgn(
+        "[FEN \"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 12\"]\n\n12...Nf6 13.Nc3 *",
+    )
+    .unwrap();
+    assert_eq!(sans(&black_to_move.moves), vec!["Nf6", "Nc3"]);
+
+    let castling = parse_pgn("[FEN \"4k3/8/8/8/8/8/8/4K2R w K - 0 1\"]\n\n1.0-0 Kd7 *").unwrap();
+    assert_eq!(sans(&castling.moves), vec!["0-0", "Kd7"]);
+    assert!(castling.moves[0].mv.castle);
+}
+
+#[test]
+fn errors_point_at_the_offending_token() {
+    assert_eq!(
+        parse_pgn("1. e4 e5\n2. Nf3 Nf4 *").unwrap_err(),
+        PgnError::new(
+            2,
+            8,
+            PgnErrorKind::InvalidMove(SanError::NoLegalMove(String::from("Nf4")))
+        )
+    );
+    assert_eq!(
+        parse_pgn("1.e4 e5 2.Nf9 *").unwrap_err(),
+        PgnError::new(
+            1,
+            11,
+            PgnErrorKind::InvalidMove(SanError::InvalidSyntax(String::from("Nf9")))
+        )
+    );
+    assert_eq!(
+        parse_pgn("1. e4 {never closed").unwrap_err(),
+        PgnError::new(1, 7, PgnErrorKind::UnterminatedComment)
+    );
+    assert_eq!(
+        parse_pgn("1. e4 e5\n(1... c5 2. Nf3").unwrap_err(),
+        PgnError::new(2, 1, PgnErrorKind::UnterminatedVariation)
+    );
+    assert_eq!(
+        parse_pgn("[Event \"Unterminated]\n1. e4 *").unwrap_err(),
+        PgnError::new(1, 1, PgnErrorKind::UnterminatedTag)
+    );
+    assert_eq!(
+        parse_pgn("1. e4 ) *").unwrap_err(),
+        PgnError::new(1, 7, PgnErrorKind::UnexpectedToken(String::from(")")))
+    );
+}
+
+#[test]
+fn movetext_after_the_result_is_an_error() {
+    assert_eq!(
+        parse_pgn("1. e4 e5 * 1. d4").unwrap_err(),
+        PgnError::new(1, 15, PgnErrorKind::UnexpectedToken(String::from("d4")))
+    );
+    assert_eq!(
+        parse_pgn("1. e4 e5 1-0 {Resigned}").unwrap_err(),
+        PgnError::new(1, 14, PgnErrorKind::UnexpectedToken(String::from("{")))
+    );
+
+    //The tags of the next game end the first one
+    let game = parse_pgn("1. e4 e5 *\n\n[Event \"Next\"]\n\n1. d4 *").unwrap();
+    assert_eq!(sans(&game.moves), vec!["e4", "e5"]);
+}
+
+#[test]
+fn games_are_replayed_after_a_dead_position() {
+    let pgn = "[FEN \"8/8/8/4k3/8/8/8/3BK3 w - - 0 1\"]\n\n1. Bc2 Kd5 2. Kd2 Kc4 *";
+
+    let game = parse_pgn(pgn).unwrap();
+    assert_eq!(sans(&game.moves), vec!["Bc2", "Kd5", "Kd2", "Kc4"]);
+
+    let board = Board::from_pgn(pgn).unwrap();
+    assert_eq!(
+        board.get_outcome(),
+        GameOutcome::Draw {
+            reason: DrawReason::InsufficientMaterial
+        }
+    );
+    assert_eq!(board.to_fen(), "8/8/8/8/2k5/8/2BK4/8 w - - 4 3");
+}
+
+#[test]
+fn games_are_replayed_past_the_seventy_five_move_rule() {
+    let pgn = "[FEN \"4k3/8/8/8/8/8/8/R3K3 w - - 148 90\"]\n\n90. Ra2 Kd7 91. Ra3 Kc6 92. Ra4 *";
+
+    let game = parse_pgn(pgn).unwrap();
+    assert_eq!(game.moves.len(), 5);
+
+    let board = Board::from_pgn(pgn).unwrap();
+    assert_eq!(board.get_halfmove_clock(), 153);
+    assert_eq!(
+        board.get_outcome(),
+        GameOutcome::Draw {
+            reason: DrawReason::SeventyFiveMoveRule
+        }
+    );
+
+    //Exporting replays the moves too
+    let exported = board.to_pgn(&[]);
+    assert!(exported.contains("90. Ra2 Kd7 91. Ra3 Kc6 92. Ra4 1/2-1/2"));
+}
+
+#[test]
+fn exported_games_parse_back() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(
+        &mut board,
+        "e4 e5 Nf3 Nc6 Bb5 a6 Ba4 Nf6 O-O Be7 Re1 b5 Bb3 d6 c3 O-O h3 Nb8 d4 Nbd7 \
+         c4 c6 cxb5 axb5 Nc3 Bb7 Bg5 b4 Nb1 h6 Bh4 c5 dxe5 Nxe4 Bxe7 Qxe7",
+    );
+
+    let tags = [(String::from("Event"), String::from("Round trip"))];
+    let pgn = board.to_pgn(&tags);
+    assert!(pgn.lines().all(|line| line.len() <= 80));
+
+    let game = parse_pgn(&pgn).unwrap();
+    assert!(game.tags.contains(&tags[0]));
+    assert_eq!(game.result, "*");
+    assert_eq!(game.moves.len(), 36);
+    assert_eq!(Board::from_pgn(&pgn).unwrap().to_fen(), board.to_fen());
+}
+
+#[test]
+fn exported_games_from_a_position_parse_back() {
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 12";
+    let mut board = Board::from_fen(fen);
+    play(&mut board, "f6 d4 g5 Qh5#");
+
+    let pgn = board.to_pgn(&[]);
+    assert!(pgn.contains("[FEN \"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 12\"]"));
+    assert!(pgn.contains("12... f6 13. d4 g5 14. Qh5# 1-0"));
+
+    let game = parse_pgn(&pgn).unwrap();
+    assert_eq!(sans(&game.moves), vec!["f6", "d4", "g5", "Qh5#"]);
+    assert_eq!(game.result, "1-0");
+    assert_eq!(Board::from_pgn(&pgn).unwrap().to_fen(), board.to_fen());
+}
+
+const DATABASE: &str = "[Event \"One\"]
+[Result \"1-0\"]
+
+1. e4 {A comment
+[that looks like a tag]
+over three lines} e5 1-0
+
+[Event \"Broken\"]
+
+1. e4 Nf9 *
+
+[Event \"Three\"]
+
+1. d4 d5 *
+";
+
+#[test]
+fn reader_splits_games_and_resumes_after_errors() {
+    let games: Vec<_> = PgnReader::new(Cursor::new(DATABASE)).collect();
+    assert_eq!(games.len(), 3);
+
+    let first = games[0].as_ref().unwrap();
+    assert_eq!(sans(&first.moves), vec!["e4", "e5"]);
+    assert_eq!(
+        first.moves[0].comments,
+        vec!["A comment\n[that looks like a tag]\nover three lines"]
+    );
+    assert_eq!(first.result, "1-0");
+
+    let error = games[1].as_ref().unwrap_err();
+    assert_eq!((error.line, error.column), (10, 7));
+
+    let third = games[2].as_ref().unwrap();
+    assert_eq!(third.tags[0].1, "Three");
+    assert_eq!(sans(&third.moves), vec!["d4", "d5"]);
+}
+
+#[test]
+fn headers_only_reader_skips_movetext() {
+    let games: Vec<_> = PgnReader::headers_only(Cursor::new(DATABASE))
+        .map(|game| game.unwrap())
+        .collect();
+
+    let events: Vec<&str> = games.iter().map(|game| game.tags[0].1.as_str()).collect();
+    assert_eq!(events, vec!["One", "Broken", "Three"]);
+    assert!(games.iter().all(|game| game.moves.is_empty()));
+    assert_eq!(games[0].result, "1-0");
+}
+
+#[test]
+fn reader_splits_games_without_tags() {
+    //Blank lines only end a game after its result
+    let database = "1. e4 e5 *\n\n1. d4 {Queen's pawn} d5\n\n2. c4 {A comment spanning\n\na blank line} 1/2-1/2\n\n1. c4 0-1\n";
+    let games: Vec<_> = PgnReader::new(Cursor::new(database))
+        .map(|game| game.unwrap())
+        .collect();
+
+    assert_eq!(games.len(), 3);
+    assert_eq!(sans(&games[0].moves), vec!["e4", "e5"]);
+    assert_eq!(sans(&games[1].moves), vec!["d4", "d5", "c4"]);
+    assert_eq!(games[1].result, "1/2-1/2");
+    assert_eq!(sans(&games[2].moves), vec!["c4"]);
+    assert_eq!(games[2].result, "0-1");
+
+    assert_eq!(PgnReader::headers_only(Cursor::new(database)).count(), 3);
+}
@@ -0,0 +1,115 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    moves::IllegalMove,
+    outcome::{DrawReason, GameOutcome},
+};
+
+//Knight moves that bring both sides back to where they started
+const KNIGHT_SHUFFLE: &str = "g1f3 g8f6 f3g1 f6g8";
+
+fn play(board: &mut Board, moves: &str) {
+    for uci in moves.split_whitespace() {
+        let mv = board.parse_uci(uci).unwrap();
+        board.make_move(mv, false).unwrap();
+    }
+}
+
+#[test]
+fn threefold_repetition_can_be_claimed() {
+    let mut board = Board::from_fen(STARTING_FEN);
+
+    play(&mut board, KNIGHT_SHUFFLE);
+    assert_eq!(board.get_repetitions(), 2);
+    assert_eq!(board.get_claimable_draw(), None);
+    assert_eq!(board.claim_draw(), None);
+
+    play(&mut board, KNIGHT_SHUFFLE);
+    assert_eq!(board.get_repetitions(), 3);
+    assert_eq!(
+        board.get_claimable_draw(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+    assert_eq!(board.get_outcome(), GameOutcome::Ongoing);
+
+    //The claim is lost once the position changes
+    play(&mut board, "e2e4");
+    assert_eq!(board.get_claimable_draw(), None);
+}
+
+#[test]
+fn claiming_a_draw_ends_the_game() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, KNIGHT_SHUFFLE);
+    play(&mut board, KNIGHT_SHUFFLE);
+
+    let draw = GameOutcome::Draw {
+        reason: DrawReason::ThreefoldRepetition,
+    };
+
+    assert_eq!(board.claim_draw(), Some(draw));
+    assert_eq!(board.get_outcome(), draw);
+    assert_eq!(board.get_claimable_draw(), None);
+
+    let mv = board.parse_uci("e2e4").unwrap();
+    assert_eq!(
+        board.make_move(mv, false).unwrap_err(),
+        IllegalMove::GameOver(draw)
+    );
+}
+
+#[test]
+fn fivefold_repetition_ends_the_game() {
+    let mut board = Board::from_fen(STARTING_FEN);
+
+    for _ in 0..3 {
+        play(&mut board, KNIGHT_SHUFFLE);
+    }
+    assert_eq!(board.get_repetitions(), 4);
+    assert_eq!(board.get_outcome(), GameOutcome::Ongoing);
+
+    play(&mut board, KNIGHT_SHUFFLE);
+    assert_eq!(board.get_repetitions(), 5);
+
+    let draw = GameOutcome::Draw {
+        reason: DrawReason::FivefoldRepetition,
+    };
+    assert_eq!(board.get_outcome(), draw);
+    assert_eq!(board.get_claimable_draw(), None);
+    assert_eq!(board.claim_draw(), None);
+
+    let mv = board.parse_uci("e2e4").unwrap();
+    assert_eq!(
+        board.make_move(mv, false).unwrap_err(),
+        IllegalMove::GameOver(draw)
+    );
+}
+
+#[test]
+fn en_passant_square_without_a_capture_is_the_same_position() {
+    //No black pawn stands next to e4, so the position after the double step is
+    //repeated by the knight moves
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "e2e4");
+    assert_eq!(board.get_en_passant_square().unwrap().to_string(), "e3");
+
+    play(&mut board, "g8f6 g1f3 f6g8 f3g1");
+    assert_eq!(board.get_en_passant_square(), None);
+    assert_eq!(board.get_repetitions(), 2);
+
+    play(&mut board, "g8f6 g1f3 f6g8 f3g1");
+    assert_eq!(board.get_repetitions(), 3);
+}
+
+#[test]
+fn en_passant_capture_makes_a_different_position() {
+    //The d4 pawn can take on e3 right after the double step, but not once the
+    //knights have moved
+    let mut board = Board::from_fen("4k1n1/8/8/8/3p4/8/4P3/4K1N1 w - - 0 1");
+    play(&mut board, "e2e4");
+
+    play(&mut board, "g8f6 g1f3 f6g8 f3g1");
+    assert_eq!(board.get_repetitions(), 1);
+
+    play(&mut board, "g8f6 g1f3 f6g8 f3g1");
+    assert_eq!(board.get_repetitions(), 2);
+}
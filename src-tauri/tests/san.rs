@@ -0,0 +1,100 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    san::SanError,
+    square::Square,
+};
+
+fn sq(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+//The parsed move in UCI notation, which spells out the squares and the promotion
+fn parse(fen: &str, san: &str) -> String {
+    Board::from_fen(fen).parse_san(san).unwrap().to_uci(false)
+}
+
+fn parse_error(fen: &str, san: &str) -> SanError {
+    Board::from_fen(fen).parse_san(san).unwrap_err()
+}
+
+#[test]
+fn promotion_with_and_without_equals_sign() {
+    let fen = "7k/P7/8/8/8/8/8/K7 w - - 0 1";
+
+    assert_eq!(parse(fen, "a8=Q"), "a7a8q");
+    assert_eq!(parse(fen, "a8Q"), "a7a8q");
+    assert_eq!(parse(fen, "a8=N+"), "a7a8n");
+    assert_eq!(parse(fen, "a8R"), "a7a8r");
+    assert_eq!(
+        parse_error(fen, "a8=K"),
+        SanError::InvalidSyntax(String::from("a8=K"))
+    );
+}
+
+#[test]
+fn castling_with_letters_and_zeros() {
+    let fen = "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1";
+    let board = Board::from_fen(fen);
+
+    assert!(board.parse_san("O-O").unwrap().castle);
+    assert_eq!(parse(fen, "O-O"), "e1g1");
+    assert_eq!(parse(fen, "0-0"), "e1g1");
+    assert_eq!(parse(fen, "O-O-O"), "e1c1");
+    assert_eq!(parse(fen, "0-0-0"), "e1c1");
+}
+
+#[test]
+fn pawn_captures_need_the_file() {
+    let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+
+    assert_eq!(parse(fen, "exd5"), "e4d5");
+    assert_eq!(
+        parse_error(fen, "xd5"),
+        SanError::InvalidSyntax(String::from("xd5"))
+    );
+    assert_eq!(
+        parse_error(fen, "e4d5"),
+        SanError::InvalidSyntax(String::from("e4d5"))
+    );
+}
+
+#[test]
+fn disambiguation_by_file_rank_and_square() {
+    let by_file = "3k4/8/8/8/8/8/4K3/R2n3R w - - 0 1";
+    assert_eq!(parse(by_file, "Raxd1"), "a1d1");
+    assert_eq!(parse(by_file, "Rhxd1"), "h1d1");
+
+    let by_rank = "3k4/8/8/R7/8/8/8/R3K3 w - - 0 1";
+    assert_eq!(parse(by_rank, "R1a3"), "a1a3");
+    assert_eq!(parse(by_rank, "R5a3"), "a5a3");
+
+    let by_square = "3k4/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1";
+    assert_eq!(parse(by_square, "Qa1b2"), "a1b2");
+
+    let board = Board::from_fen(by_square);
+    let mv = board.parse_san("Qa1b2").unwrap();
+    assert_eq!(board.to_san(mv).unwrap(), "Qa1b2");
+}
+
+#[test]
+fn every_error_variant() {
+    assert_eq!(
+        parse_error(STARTING_FEN, "Zz9"),
+        SanError::InvalidSyntax(String::from("Zz9"))
+    );
+    assert_eq!(
+        parse_error(STARTING_FEN, "Ne4"),
+        SanError::NoLegalMove(String::from("Ne4"))
+    );
+    assert_eq!(
+        parse_error("7k/P7/8/8/8/8/8/K7 w - - 0 1", "a8"),
+        SanError::MissingPromotionChoice(String::from("a8"))
+    );
+    assert_eq!(
+        parse_error("3k4/8/8/8/8/8/4K3/R2n3R w - - 0 1", "Rxd1"),
+        SanError::AmbiguousMove {
+            san: String::from("Rxd1"),
+            from: vec![sq("a1"), sq("h1")],
+        }
+    );
+}
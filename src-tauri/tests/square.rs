@@ -0,0 +1,56 @@
+use rust_chess::game::square::{Square, SquareError};
+
+#[test]
+fn algebraic_names_round_trip() {
+    for square in Square::all() {
+        let name = square.to_string();
+        assert_eq!(name.parse::<Square>().unwrap(), square, "{}", name);
+    }
+
+    assert_eq!("a8".parse::<Square>().unwrap(), Square::A8);
+    assert_eq!("h1".parse::<Square>().unwrap(), Square::H1);
+    assert_eq!(Square::A1.to_string(), "a1");
+    assert_eq!(Square::E8.to_string(), "e8");
+}
+
+#[test]
+fn indices_follow_the_drawn_board() {
+    assert_eq!(Square::try_from(0).unwrap(), Square::A8);
+    assert_eq!(Square::try_from(7).unwrap(), Square::H8);
+    assert_eq!(Square::try_from(56).unwrap(), Square::A1);
+    assert_eq!(Square::try_from(63).unwrap(), Square::H1);
+    assert_eq!(Square::try_from(36).unwrap().to_string(), "e4");
+
+    assert_eq!(usize::from(Square::E1), 60);
+    assert_eq!(Square::E1.file(), 4);
+    assert_eq!(Square::E1.rank(), 0);
+}
+
+#[test]
+fn out_of_range_indices() {
+    assert_eq!(Square::try_from(64), Err(SquareError::OutOfRange(64)));
+    assert_eq!(
+        Square::try_from(usize::MAX),
+        Err(SquareError::OutOfRange(usize::MAX))
+    );
+    assert_eq!(Square::new(64), None);
+    assert_eq!(Square::from_row_col(8, 0), None);
+    assert_eq!(Square::from_row_col(0, -1), None);
+    assert_eq!(Square::from_file_rank(8, 0), None);
+    assert_eq!(Square::H1.offset(1, 0), None);
+    assert_eq!(Square::H1.offset(0, 1), None);
+}
+
+#[test]
+fn invalid_names() {
+    for name in [
+        "", "a", "i9", "a0", "a9", "i1", "A1", "e44", "1a", " e4", "é4",
+    ] {
+        assert_eq!(
+            name.parse::<Square>(),
+            Err(SquareError::InvalidCoordinates(String::from(name))),
+            "'{}'",
+            name
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    piece::PieceType,
+    uci::UciError,
+};
+
+const CASTLING_FEN: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+#[test]
+fn standard_castling_is_the_king_step() {
+    let board = Board::from_fen(CASTLING_FEN);
+
+    let kingside = board.parse_uci("e1g1").unwrap();
+    assert!(kingside.castle);
+    assert_eq!(kingside.to_uci(false), "e1g1");
+
+    let queenside = board.parse_uci("e1c1").unwrap();
+    assert!(queenside.castle);
+    assert_eq!(queenside.to_uci(false), "e1c1");
+}
+
+#[test]
+fn chess960_castling_is_the_king_taking_its_rook() {
+    let board = Board::from_fen(CASTLING_FEN);
+
+    let kingside = board.parse_uci("e1h1").unwrap();
+    assert!(kingside.castle);
+    assert_eq!(kingside.to_uci(true), "e1h1");
+    assert_eq!(kingside.to_uci(false), "e1g1");
+
+    let queenside = board.parse_uci("e1a1").unwrap();
+    assert!(queenside.castle);
+    assert_eq!(queenside.to_uci(true), "e1a1");
+    assert_eq!(queenside.to_uci(false), "e1c1");
+
+    let black = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1");
+    assert_eq!(black.parse_uci("e8h8").unwrap().to_uci(false), "e8g8");
+    assert_eq!(black.parse_uci("e8a8").unwrap().to_uci(false), "e8c8");
+
+    //Other moves are written the same way in both modes
+    let board = Board::from_fen(STARTING_FEN);
+    assert_eq!(board.parse_uci("g1f3").unwrap().to_uci(true), "g1f3");
+}
+
+#[test]
+fn promotions_end_with_the_piece() {
+    let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+
+    let queen = board.parse_uci("a7a8q").unwrap();
+    assert_eq!(queen.promotion_piece, Some(PieceType::Queen));
+    assert_eq!(queen.to_uci(false), "a7a8q");
+
+    let knight = board.parse_uci("a7b8N").unwrap();
+    assert_eq!(knight.promotion_piece, Some(PieceType::Knight));
+    assert_eq!(knight.to_uci(false), "a7b8n");
+
+    //The promotion piece must be given
+    assert_eq!(
+        board.parse_uci("a7a8").unwrap_err(),
+        UciError::NoLegalMove(String::from("a7a8"))
+    );
+}
+
+#[test]
+fn en_passant_is_filled_in() {
+    let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2");
+
+    assert!(board.parse_uci("e5d6").unwrap().en_passant);
+}
+
+#[test]
+fn malformed_moves() {
+    let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+
+    for uci in [
+        "", "e1", "e1e2e3", "i1e2", "e0e1", "e1e9", "a7a8k", "a7a8x", "é1e2",
+    ] {
+        assert_eq!(
+            board.parse_uci(uci).unwrap_err(),
+            UciError::InvalidSyntax(String::from(uci)),
+            "'{}' is malformed",
+            uci
+        );
+    }
+}
+
+#[test]
+fn illegal_moves() {
+    let board = Board::from_fen(STARTING_FEN);
+
+    for uci in ["e2e5", "e7e5", "e1g1", "e1h1", "d1d3", "a3a4", "e2e4q"] {
+        assert_eq!(
+            board.parse_uci(uci).unwrap_err(),
+            UciError::NoLegalMove(String::from(uci)),
+            "'{}' is illegal",
+            uci
+        );
+    }
+}
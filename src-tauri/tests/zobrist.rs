@@ -0,0 +1,90 @@
+use rust_chess::game::{
+    board::{Board, STARTING_FEN},
+    outcome::DrawReason,
+};
+
+//Keys from the Polyglot book format specification
+const POLYGLOT_KEYS: [(&str, u64); 9] = [
+    ("", 0x463b96181691fc9c),
+    ("e2e4", 0x823c9b50fd114196),
+    ("e2e4 d7d5", 0x0756b94461c50fb0),
+    ("e2e4 d7d5 e4e5", 0x662fafb965db29d4),
+    ("e2e4 d7d5 e4e5 f7f5", 0x22a48b5a8e47ff78),
+    ("e2e4 d7d5 e4e5 f7f5 e1e2", 0x652a607ca3f242c1),
+    ("e2e4 d7d5 e4e5 f7f5 e1e2 e8f7", 0x00fdd303c946bdd9),
+    ("a2a4 b7b5 h2h4 b5b4 c2c4", 0x3c8123ea7b067637),
+    ("a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3", 0x5c3f9b829b279560),
+];
+
+fn play(board: &mut Board, moves: &str) {
+    for uci in moves.split_whitespace() {
+        let mv = board.parse_uci(uci).unwrap();
+        board.make_move(mv, false).unwrap();
+    }
+}
+
+#[test]
+fn incremental_hash_matches_polyglot() {
+    for (moves, key) in POLYGLOT_KEYS {
+        let mut board = Board::from_fen(STARTING_FEN);
+        play(&mut board, moves);
+
+        assert_eq!(board.get_hash(), key, "hash after '{}'", moves);
+        assert_eq!(board.compute_hash(), key, "hash after '{}'", moves);
+    }
+}
+
+#[test]
+fn hash_from_fen_matches_played_moves() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "e2e4 d7d5 e4e5 f7f5");
+
+    let loaded = Board::from_fen(&board.to_fen());
+    assert_eq!(loaded.get_hash(), board.get_hash());
+}
+
+#[test]
+fn unmake_restores_the_hash() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    let mut hashes = vec![board.get_hash()];
+
+    for uci in "e2e4 d7d5 e4d5 g8f6 f1b5 c7c6 d5c6 d8d2".split_whitespace() {
+        play(&mut board, uci);
+        hashes.push(board.get_hash());
+    }
+
+    while board.unmake_move(false).is_some() {
+        hashes.pop();
+        assert_eq!(board.get_hash(), *hashes.last().unwrap());
+    }
+}
+
+#[test]
+fn castling_rights_change_the_hash() {
+    let with_rights = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    let without_rights = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1");
+
+    assert_ne!(with_rights.get_hash(), without_rights.get_hash());
+}
+
+#[test]
+fn repetitions_are_found_by_hash() {
+    let mut board = Board::from_fen(STARTING_FEN);
+    play(&mut board, "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8");
+
+    assert_eq!(board.get_repetitions(), 3);
+}
+
+#[test]
+fn illegal_en_passant_doesnt_break_repetitions() {
+    //The e5 pawn is pinned, so d5 leaves no en passant capture to play and the position
+    //after it is repeated by the king moves, although its Polyglot hash differs
+    let mut board = Board::from_fen("4r1k1/3p4/8/4P3/8/8/8/4K3 b - - 0 1");
+    play(&mut board, "d7d5 e1d1 g8h8 d1e1 h8g8 e1d1 g8h8 d1e1 h8g8");
+
+    assert_eq!(board.get_repetitions(), 3);
+    assert_eq!(
+        board.get_claimable_draw(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+}
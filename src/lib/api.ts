@@ -1,5 +1,13 @@
 import { invoke } from "@tauri-apps/api";
-import type { Board, Move, Piece } from "./models";
+import {
+  Team,
+  type AttackMap,
+  type Board,
+  type GameOutcome,
+  type Move,
+  type MoveResponse,
+  type Piece,
+} from "./models";
 
 export const api_get_board = async (): Promise<Board> => {
   let result: Board;
@@ -10,18 +18,18 @@ export const api_get_board = async (): Promise<Board> => {
   return result;
 };
 
-export const api_get_piece = async (index: number): Promise<Piece | null> => {
+export const api_get_piece = async (square: number): Promise<Piece | null> => {
   let result: Piece | null = null;
-  await invoke("get_piece", { index: index }).then(
+  await invoke("get_piece", { square: square }).then(
     (res: Piece | null) => (result = res)
   );
 
   return result;
 };
 
-export const api_get_position = async (index: number): Promise<String> => {
+export const api_get_position = async (square: number): Promise<String> => {
   let result: String | null = null;
-  await invoke("get_position", { index: index }).then((res: String) => {
+  await invoke("get_position", { square: square }).then((res: String) => {
     result = res;
   });
 
@@ -32,9 +40,118 @@ export const api_reset_board = async () => {
   await invoke("reset_board");
 };
 
-export const api_make_move = async (mv: Move): Promise<Board> => {
+export const api_make_move = async (mv: Move): Promise<MoveResponse> => {
+  let result: MoveResponse | null = null;
+  await invoke("make_move", { mv: mv }).then((res: MoveResponse) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_make_san_move = async (san: string): Promise<MoveResponse> => {
+  let result: MoveResponse | null = null;
+  await invoke("make_san_move", { san: san }).then((res: MoveResponse) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_get_san = async (mv: Move): Promise<String> => {
+  let result: String | null = null;
+  await invoke("get_san", { mv: mv }).then((res: String) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_get_outcome = async (): Promise<GameOutcome> => {
+  let result: GameOutcome | null = null;
+  await invoke("get_outcome").then((res: GameOutcome) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_get_fen = async (): Promise<String> => {
+  let result: String | null = null;
+  await invoke("get_fen").then((res: String) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_load_fen = async (fen: string): Promise<Board> => {
+  let result: Board | null = null;
+  await invoke("load_fen", { fen: fen }).then((res: Board) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_load_pgn = async (pgn: string): Promise<Board> => {
+  let result: Board | null = null;
+  await invoke("load_pgn", { pgn: pgn }).then((res: Board) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_export_pgn = async (): Promise<string> => {
+  let result: string | null = null;
+  await invoke("export_pgn").then((res: string) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_undo_move = async (): Promise<Board> => {
+  let result: Board | null = null;
+  await invoke("undo_move").then((res: Board) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_redo_move = async (): Promise<Board> => {
   let result: Board | null = null;
-  await invoke("make_move", { mv: mv }).then((res: Board) => {
+  await invoke("redo_move").then((res: Board) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_claim_draw = async (): Promise<GameOutcome> => {
+  let result: GameOutcome | null = null;
+  await invoke("claim_draw").then((res: GameOutcome) => {
+    result = res;
+  });
+
+  return result;
+};
+
+export const api_has_mating_material = async (team: Team): Promise<boolean> => {
+  let result: boolean = false;
+  await invoke("has_mating_material", { team: Team[team] }).then(
+    (res: boolean) => (result = res)
+  );
+
+  return result;
+};
+
+//The attack map of the opponent of the side to move
+export const api_get_threat_map = async (): Promise<AttackMap> => {
+  let result: AttackMap | null = null;
+  await invoke("get_threat_map").then((res: AttackMap) => {
     result = res;
   });
 
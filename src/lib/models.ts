@@ -18,12 +18,28 @@ export type Board = {
   available_moves: Array<Move>;
   current_player: Team;
   turn: number;
-  en_passant: boolean;
-  last_moved_piece: number;
+  en_passant: number | null;
   check: boolean;
-  mate: boolean;
+  outcome: GameOutcome;
+  halfmove_clock: number;
+  claimable_draw: DrawReason | null;
+  repetitions: number;
 };
 
+export enum DrawReason {
+  Stalemate = "Stalemate",
+  InsufficientMaterial = "InsufficientMaterial",
+  FiftyMoveRule = "FiftyMoveRule",
+  SeventyFiveMoveRule = "SeventyFiveMoveRule",
+  ThreefoldRepetition = "ThreefoldRepetition",
+  FivefoldRepetition = "FivefoldRepetition",
+}
+
+export type GameOutcome =
+  | { status: "Ongoing" }
+  | { status: "Checkmate"; winner: string }
+  | { status: "Draw"; reason: DrawReason };
+
 export type Piece = {
   piece_type: PieceType;
   team: Team;
@@ -35,5 +51,26 @@ export type Move = {
   from: number;
   to: number;
   en_passant: boolean;
+  castle: boolean;
   promotion_piece: PieceType | null;
 };
+
+export type MoveOutcome = {
+  mv: Move;
+  captured: PieceType | null;
+  castle: boolean;
+  promotion: PieceType | null;
+  check: boolean;
+  status: GameOutcome;
+};
+
+export type MoveResponse = {
+  board: Board;
+  san: string;
+  outcome: MoveOutcome;
+};
+
+export type AttackMap = {
+  team: string;
+  counts: Array<number>;
+};